@@ -0,0 +1,46 @@
+#![cfg(test)]
+extern crate lib;
+
+#[macro_use]
+extern crate criterion;
+
+use lib::chess::leapers::*;
+use lib::chess::{BitBoard, BitPosition, Player};
+
+use criterion::Criterion;
+
+// Mirrors benches/sliding_attacks.rs so the per-square numbers compare
+fn every_square<F: Fn(BitPosition) -> BitBoard>(find_moves: F) -> BitBoard {
+    (0..64).fold(BitBoard::empty(), |acc, index| {
+        acc.join(find_moves(BitPosition::from(index)))
+    })
+}
+
+fn leaper_attacks(c: &mut Criterion) {
+    c.bench_function("knight shifts from every square", |b| {
+        b.iter(|| every_square(|position| knight_shifts(position.into())))
+    });
+
+    c.bench_function("knight table from every square", |b| {
+        b.iter(|| every_square(knight_attacks))
+    });
+
+    c.bench_function("king shifts from every square", |b| {
+        b.iter(|| every_square(|position| king_shifts(position.into())))
+    });
+
+    c.bench_function("king table from every square", |b| {
+        b.iter(|| every_square(king_attacks))
+    });
+
+    c.bench_function("pawn shifts from every square", |b| {
+        b.iter(|| every_square(|position| pawn_attack_shifts(Player::White, position.into())))
+    });
+
+    c.bench_function("pawn table from every square", |b| {
+        b.iter(|| every_square(|position| pawn_attacks(Player::White, position)))
+    });
+}
+
+criterion_group!(benches, leaper_attacks);
+criterion_main!(benches);
@@ -0,0 +1,44 @@
+#![cfg(test)]
+extern crate lib;
+
+#[macro_use]
+extern crate criterion;
+
+use lib::chess::{BitBoard, BitPosition, Board};
+
+use criterion::{black_box, Criterion};
+
+const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+// Looks up the slides from every square, so the numbers don't depend on
+//  where the fixture happens to put its pieces
+fn every_square<F: Fn(BitPosition) -> BitBoard>(find_moves: F) -> BitBoard {
+    (0..64).fold(BitBoard::empty(), |acc, index| {
+        acc.join(find_moves(BitPosition::from(index)))
+    })
+}
+
+fn sliding_attacks(c: &mut Criterion) {
+    let board = Board::from_fen(KIWIPETE).unwrap();
+    c.bench_function("rook moves from every square", move |b| {
+        b.iter(|| every_square(|position| board.find_rook_moves(position, position.into())))
+    });
+
+    let board = Board::from_fen(KIWIPETE).unwrap();
+    c.bench_function("bishop moves from every square", move |b| {
+        b.iter(|| every_square(|position| board.find_bishop_moves(position, position.into())))
+    });
+
+    let board = Board::from_fen(KIWIPETE).unwrap();
+    c.bench_function("queen moves from every square", move |b| {
+        b.iter(|| every_square(|position| board.find_queen_moves(position, position.into())))
+    });
+
+    let board = Board::from_fen(KIWIPETE).unwrap();
+    c.bench_function("kiwipete perft 2", move |b| {
+        b.iter(|| black_box(&board).perft(2))
+    });
+}
+
+criterion_group!(benches, sliding_attacks);
+criterion_main!(benches);
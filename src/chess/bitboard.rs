@@ -25,8 +25,8 @@ pub const RANK_8: BitBoard = BitBoard::new(0x8080_8080_8080_8080);
 pub const ENDS: BitBoard = BitBoard::new(FILE_A.board | FILE_H.board);
 pub const SIDES: BitBoard = BitBoard::new(RANK_1.board | RANK_8.board);
 
-pub const QUEENSIDE_CASTLE: BitBoard = BitBoard::new(0b0110_0000);
-pub const KINGSIDE_CASTLE: BitBoard = BitBoard::new(0b0000_1110);
+pub const QUEENSIDE_CASTLE: BitBoard = BitBoard::new(0b0000_1110);
+pub const KINGSIDE_CASTLE: BitBoard = BitBoard::new(0b0110_0000);
 pub const CASTLE_CHECK: BitBoard = BitBoard::new(0b0110_1100 << 56 | 0b0110_1100);
 
 pub const RANKS: [BitBoard; 8] = [
@@ -130,6 +130,12 @@ impl From<u64> for BitBoard {
     }
 }
 
+impl From<BitBoard> for u64 {
+    fn from(bitboard: BitBoard) -> Self {
+        bitboard.board
+    }
+}
+
 impl From<BitPosition> for BitBoard {
     fn from(position: BitPosition) -> Self {
         BitBoard {
@@ -214,6 +220,11 @@ impl BitBoard {
         BitBoard::from(self.board & rhs.board)
     }
 
+    pub fn toggle(self, rhs: BitBoard) -> Self {
+        covered_by!("BitBoard::toggle");
+        BitBoard::from(self.board ^ rhs.board)
+    }
+
     pub fn inverse(self) -> Self {
         covered_by!("BitBoard::inverse");
         BitBoard::from(!self.board)
@@ -559,6 +570,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_toggle() {
+        covers!("BitBoard::toggle");
+
+        assert_eq!(
+            BitBoard::new(A).toggle(BitBoard::new(B)),
+            BitBoard::new(0b101101110)
+        );
+        assert_eq!(
+            BitBoard::new(A)
+                .toggle(BitBoard::new(B))
+                .toggle(BitBoard::new(B)),
+            BitBoard::new(A)
+        );
+    }
+
     #[test]
     fn test_inverse() {
         covers!("BitBoard::inverse");
@@ -1,130 +1,95 @@
 use super::Board;
 
+use crate::chess::{leapers, magic};
 use crate::chess::{BitBoard, BitPosition, PieceType, Player};
 
 impl Board {
-    fn rook_queen_threats(
-        &self,
-        current_position: BitPosition,
-        current_position_mask: BitBoard,
-        enemy_mask: BitBoard,
-    ) -> bool {
-        !self
-            .find_rook_moves(current_position, current_position_mask)
-            .intersect(
-                self.pieces[PieceType::Rook as usize].join(self.pieces[PieceType::Queen as usize]),
-            )
-            .intersect(enemy_mask)
-            .is_empty()
-    }
+    fn rook_queen_threats(&self, current_position: BitPosition, occupancy: BitBoard) -> BitBoard {
+        let threats = magic::rook_attacks(current_position, occupancy).intersect(
+            self.pieces[PieceType::Rook as usize].join(self.pieces[PieceType::Queen as usize]),
+        );
 
-    fn bishop_queen_threats(
-        &self,
-        current_position: BitPosition,
-        current_position_mask: BitBoard,
-        enemy_mask: BitBoard,
-    ) -> bool {
-        !self
-            .find_bishop_moves(current_position, current_position_mask)
-            .intersect(
-                self.pieces[PieceType::Bishop as usize]
-                    .join(self.pieces[PieceType::Queen as usize]),
-            )
-            .intersect(enemy_mask)
-            .is_empty()
-    }
+        if !threats.is_empty() {
+            covered_by!("MoveGenerator::rook_attacks");
+        }
 
-    fn knight_threats(
-        &self,
-        current_position: BitPosition,
-        current_position_mask: BitBoard,
-        enemy_mask: BitBoard,
-    ) -> bool {
-        !self
-            .find_knight_moves(current_position, current_position_mask)
-            .intersect(self.pieces[PieceType::Knight as usize])
-            .intersect(enemy_mask)
-            .is_empty()
+        threats
     }
 
-    fn pawn_threats(
-        &self,
-        player: Player,
-        current_position_mask: BitBoard,
-        enemy_mask: BitBoard,
-    ) -> bool {
-        // We don't use normal pawn generation because there's a lot of stuff
-        //  pawns do that doesn't include capturing
-        let pawn_aoe = match player {
-            Player::White => current_position_mask
-                .shift(1, 1)
-                .join(current_position_mask.shift(1, -1)),
-            Player::Black => current_position_mask
-                .shift(-1, 1)
-                .join(current_position_mask.shift(-1, -1)),
-        };
-
-        !pawn_aoe
-            .intersect(enemy_mask)
-            .intersect(self.pieces[PieceType::Pawn as usize])
-            .is_empty()
-    }
+    fn bishop_queen_threats(&self, current_position: BitPosition, occupancy: BitBoard) -> BitBoard {
+        let threats = magic::bishop_attacks(current_position, occupancy).intersect(
+            self.pieces[PieceType::Bishop as usize].join(self.pieces[PieceType::Queen as usize]),
+        );
 
-    // TODO: Test
-    fn king_threats(
-        &self,
-        current_position: BitPosition,
-        current_position_mask: BitBoard,
-        enemy_mask: BitBoard,
-    ) -> bool {
-        !self
-            .find_king_moves(current_position, current_position_mask)
-            .intersect(self.pieces[PieceType::King as usize])
-            .intersect(enemy_mask)
-            .is_empty()
+        if !threats.is_empty() {
+            covered_by!("MoveGenerator::bishop_attacks");
+        }
+
+        threats
     }
 
-    fn single_check(
-        &self,
-        player: Player,
-        current_position: BitPosition,
-        current_position_mask: BitBoard,
-    ) -> bool {
-        // TODO: Sort by complexity
-        let enemy_mask = self.players[1 - player as usize];
+    fn knight_threats(&self, current_position: BitPosition) -> BitBoard {
+        let threats = leapers::knight_attacks(current_position)
+            .intersect(self.pieces[PieceType::Knight as usize]);
 
-        if self.rook_queen_threats(current_position, current_position_mask, enemy_mask) {
-            covered_by!("MoveGenerator::rook_attacks");
-            return true;
-        }
-
-        if self.knight_threats(current_position, current_position_mask, enemy_mask) {
+        if !threats.is_empty() {
             covered_by!("MoveGenerator::knight_attacks");
-            return true;
         }
 
-        if self.bishop_queen_threats(current_position, current_position_mask, enemy_mask) {
-            covered_by!("MoveGenerator::bishop_attacks");
-            return true;
-        }
+        threats
+    }
+
+    fn pawn_threats(&self, current_position: BitPosition) -> BitBoard {
+        // We don't use normal pawn generation because there's a lot of stuff
+        //  pawns do that doesn't include capturing.  A pawn attacking us
+        //  stands where an enemy pawn on our space would attack it.
+        let pawns = self.pieces[PieceType::Pawn as usize];
+        let threats = leapers::pawn_attacks(Player::White, current_position)
+            .intersect(pawns)
+            .intersect(self.players[Player::Black as usize])
+            .join(
+                leapers::pawn_attacks(Player::Black, current_position)
+                    .intersect(pawns)
+                    .intersect(self.players[Player::White as usize]),
+            );
 
-        if self.pawn_threats(player, current_position_mask, enemy_mask) {
+        if !threats.is_empty() {
             covered_by!("MoveGenerator::pawn_attacks");
-            return true;
         }
 
-        if self.king_threats(current_position, current_position_mask, enemy_mask) {
-            return true;
-        }
+        threats
+    }
+
+    fn king_threats(&self, current_position: BitPosition) -> BitBoard {
+        leapers::king_attacks(current_position).intersect(self.pieces[PieceType::King as usize])
+    }
+
+    /// Every piece, of either player, attacking `current_position` if only
+    ///  the pieces in `occupancy` were on the board.  Removing pieces from
+    ///  `occupancy` both drops them as attackers and opens up the lines
+    ///  behind them.
+    pub fn attackers_to(&self, current_position: BitPosition, occupancy: BitBoard) -> BitBoard {
+        self.rook_queen_threats(current_position, occupancy)
+            .join(self.bishop_queen_threats(current_position, occupancy))
+            .join(self.knight_threats(current_position))
+            .join(self.pawn_threats(current_position))
+            .join(self.king_threats(current_position))
+            .intersect(occupancy)
+    }
 
-        false
+    fn single_check(&self, player: Player, current_position: BitPosition, _: BitBoard) -> bool {
+        let enemy_mask = self.players[1 - player as usize];
+
+        !self
+            .attackers_to(current_position, self.all_pieces())
+            .intersect(enemy_mask)
+            .is_empty()
     }
 
     pub fn is_attacked(&self, player: Player, mut space_mask: BitBoard) -> bool {
-        space_mask
-            .any(|current_position| {
-                self.single_check(player, current_position, current_position.into())
-            })
+        space_mask.any(|current_position| {
+            self.single_check(player, current_position, current_position.into())
+        })
     }
 }
 
@@ -162,6 +127,41 @@ mod tests {
     }
     // TODO: Test Queen, Pawn, King
 
+    #[test]
+    fn test_attackers_to() {
+        let board = Board::from_fen("3qk3/8/3rn3/2P5/8/8/3R4/3QK3 w - - 0 1").unwrap();
+        let attackers = board.attackers_to(RankFile::D4.into(), board.all_pieces());
+
+        assert_eq!(
+            attackers,
+            BitBoard::from(RankFile::D6)
+                .join(RankFile::D2.into())
+                .join(RankFile::E6.into())
+        );
+
+        // Pieces behind an attacker join in once it's gone
+        let occupancy = board.all_pieces() - RankFile::D6.into() - RankFile::D2.into();
+        assert_eq!(
+            board.attackers_to(RankFile::D4.into(), occupancy),
+            BitBoard::from(RankFile::D8)
+                .join(RankFile::D1.into())
+                .join(RankFile::E6.into())
+        );
+
+        assert_eq!(
+            board.attackers_to(RankFile::D6.into(), board.all_pieces()),
+            BitBoard::from(RankFile::C5)
+                .join(RankFile::D8.into())
+                .join(RankFile::D2.into())
+        );
+        assert_eq!(
+            board.attackers_to(RankFile::D2.into(), board.all_pieces()),
+            BitBoard::from(RankFile::D1)
+                .join(RankFile::E1.into())
+                .join(RankFile::D6.into())
+        );
+    }
+
     #[test]
     fn test_rook_attacks() {
         covers!("MoveGenerator::rook_attacks");
@@ -0,0 +1,191 @@
+use super::Board;
+
+use crate::chess::leapers;
+use crate::chess::magic;
+use crate::chess::{BitBoard, BitPosition, GenType, Move, MoveType, PieceType};
+
+impl Board {
+    /// Whether `mv`, a legal move for the player to move, leaves the other
+    ///  king in check, whether from the piece that moved or from a slider
+    ///  it uncovered.
+    pub fn gives_check(&self, mv: &Move) -> bool {
+        let enemy_king_mask = self
+            .enemy_mask()
+            .intersect(self.pieces[PieceType::King as usize]);
+
+        // Test boards may have no enemy king or several, so play it out
+        if enemy_king_mask.count_pieces() != 1 {
+            return self.apply(mv).is_king_attacked(self.next_player.opponent());
+        }
+
+        let king = enemy_king_mask.first_bit_position();
+        let to = BitPosition::from(mv.to);
+
+        let mut occupancy = (self.all_pieces() - mv.from.into()).join(to.into());
+
+        // The piece that ends up checking directly, and where it stands
+        let (piece_type, position) = match mv.move_type {
+            MoveType::Standard => (mv.piece_type, to),
+            MoveType::Promotion { promoted_to } => (promoted_to, to),
+            MoveType::EnPassant => {
+                occupancy -= BitPosition::from((mv.from.rank(), mv.to.file())).into();
+                (PieceType::Pawn, to)
+            }
+            MoveType::Castling { is_queenside } => {
+                let (rook_file, next_rook_file) = if is_queenside { (0, 3) } else { (7, 5) };
+                let next_rook_position = BitPosition::from((mv.from.rank(), next_rook_file));
+
+                occupancy -= BitPosition::from((mv.from.rank(), rook_file)).into();
+                occupancy |= next_rook_position.into();
+                (PieceType::Rook, next_rook_position)
+            }
+        };
+
+        let direct_attacks = match piece_type {
+            PieceType::Pawn => leapers::pawn_attacks(self.next_player, position),
+            PieceType::Knight => leapers::knight_attacks(position),
+            PieceType::Bishop => magic::bishop_attacks(position, occupancy),
+            PieceType::Rook => magic::rook_attacks(position, occupancy),
+            PieceType::Queen => magic::rook_attacks(position, occupancy)
+                .join(magic::bishop_attacks(position, occupancy)),
+            PieceType::King => BitBoard::empty(),
+        };
+
+        if !direct_attacks.intersect(enemy_king_mask).is_empty() {
+            return true;
+        }
+
+        // Our sliders that stayed put, which see the king once the move
+        //  clears their line
+        let sliders = self.players[self.next_player as usize].intersect(occupancy);
+        let queens = self.pieces[PieceType::Queen as usize];
+
+        !magic::rook_attacks(king, occupancy)
+            .intersect(self.pieces[PieceType::Rook as usize].join(queens))
+            .join(
+                magic::bishop_attacks(king, occupancy)
+                    .intersect(self.pieces[PieceType::Bishop as usize].join(queens)),
+            )
+            .intersect(sliders)
+            .is_empty()
+    }
+
+    // Fills in the check and checkmate flags of a legal move
+    pub(crate) fn mark_check(&self, mv: &mut Move) {
+        mv.threatens_king = self.gives_check(mv);
+        mv.is_checkmate = mv.threatens_king
+            && self
+                .apply(mv)
+                .legal_moves_unannotated(GenType::All)
+                .is_empty();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess::{RankFile, STARTING_FEN};
+
+    fn find_move(board: &Board, from: RankFile, to: RankFile) -> Move {
+        board
+            .legal_moves()
+            .iter()
+            .find(|mv| mv.from == from && mv.to == to)
+            .cloned()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_direct_and_discovered_checks() {
+        // Moving the knight off the d file uncovers the rook
+        let board = Board::from_fen("3k4/8/8/3N4/8/8/8/3RK3 w - - 0 1").unwrap();
+        assert!(find_move(&board, RankFile::D5, RankFile::C3).threatens_king);
+
+        // Qa5 checks along the diagonal as well as uncovering the rook
+        let board = Board::from_fen("3k4/8/8/3Q4/8/8/8/3RK3 w - - 0 1").unwrap();
+        let mv = find_move(&board, RankFile::D5, RankFile::A5);
+
+        assert!(mv.threatens_king);
+        assert!(!mv.is_checkmate);
+
+        // Staying on the line keeps the rook blocked
+        let board = Board::from_fen("3k4/8/8/3P4/8/8/8/3RK3 w - - 0 1").unwrap();
+        assert!(!find_move(&board, RankFile::D5, RankFile::D6).threatens_king);
+    }
+
+    #[test]
+    fn test_castling_and_promotion_checks() {
+        // The rook lands on f1 facing the king
+        let board = Board::from_fen("5k2/8/8/8/8/8/8/4K2R w K - 0 1").unwrap();
+        assert!(find_move(&board, RankFile::E1, RankFile::G1).threatens_king);
+
+        // Only the queen and rook see along the back rank
+        let board = Board::from_fen("7k/P7/8/8/8/8/8/K7 w - - 0 1").unwrap();
+        let checks: Vec<bool> = board
+            .legal_moves()
+            .iter()
+            .filter(|mv| mv.from == RankFile::A7)
+            .map(|mv| mv.threatens_king)
+            .collect();
+
+        // Queen, knight, rook and bishop in `PROMOTION_PIECES` order
+        assert_eq!(checks, vec![true, false, true, false]);
+    }
+
+    #[test]
+    fn test_en_passant_discovered_check() {
+        // Taking removes the pawn on d5 from between the bishop and the king
+        let board = Board::from_fen("8/1k6/8/3pP3/8/8/6B1/K7 w - d6 0 1").unwrap();
+        assert!(find_move(&board, RankFile::E5, RankFile::D6).threatens_king);
+
+        let board = Board::from_fen("8/8/8/1k1pP3/8/8/6B1/K7 w - d6 0 1").unwrap();
+        assert!(!find_move(&board, RankFile::E5, RankFile::D6).threatens_king);
+    }
+
+    #[test]
+    fn test_checkmate() {
+        let board = Board::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+        let mv = find_move(&board, RankFile::A1, RankFile::A8);
+
+        assert!(mv.threatens_king);
+        assert!(mv.is_checkmate);
+
+        // An escape square turns the same move into a plain check
+        let board = Board::from_fen("6k1/5pp1/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+        let mv = find_move(&board, RankFile::A1, RankFile::A8);
+
+        assert!(mv.threatens_king);
+        assert!(!mv.is_checkmate);
+    }
+
+    #[test]
+    fn test_matches_playing_it_out() {
+        for fen in [
+            STARTING_FEN,
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+        ]
+        .iter()
+        {
+            let board = Board::from_fen(fen).unwrap();
+
+            for mv in board.legal_moves_unannotated(GenType::All).iter() {
+                let child = board.apply(mv);
+
+                for reply in child.legal_moves_unannotated(GenType::All).iter() {
+                    assert_eq!(
+                        child.gives_check(reply),
+                        child
+                            .apply(reply)
+                            .is_king_attacked(child.next_player.opponent()),
+                        "{} after {}",
+                        reply,
+                        mv
+                    );
+                }
+            }
+        }
+    }
+}
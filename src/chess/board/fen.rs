@@ -0,0 +1,381 @@
+use super::Board;
+
+use crate::chess::errors::{BoardError, InvalidFenReason};
+use crate::chess::{
+    BitBoard, BitPosition, CastlingRights, Move, Piece, PieceType, Player, RankFile,
+};
+use crate::chess::{PIECE_COUNT, PLAYER_COUNT};
+
+fn fen_error(reason: InvalidFenReason) -> BoardError {
+    BoardError::InvalidFen(reason)
+}
+
+impl Board {
+    /// Parses a position in Forsyth–Edwards Notation.
+    ///
+    /// The halfmove clock and fullmove number may be omitted, in which case
+    ///  they default to `0` and `1` respectively.
+    pub fn from_fen(fen: &str) -> Result<Board, BoardError> {
+        let fields: Vec<&str> = fen.split_whitespace().collect();
+
+        if fields.len() < 4 || fields.len() > 6 {
+            return Err(fen_error(InvalidFenReason::IncorrectFieldCount));
+        }
+
+        let mut board = Board::default();
+
+        board.parse_placement(fields[0])?;
+
+        board.next_player = match fields[1] {
+            "w" => Player::White,
+            "b" => Player::Black,
+            _ => return Err(fen_error(InvalidFenReason::InvalidActivePlayer)),
+        };
+
+        board.parse_castling_rights(fields[2])?;
+        board.parse_en_passant(fields[3])?;
+
+        if let Some(halfmove_clock) = fields.get(4) {
+            board.halfmove_clock = halfmove_clock
+                .parse()
+                .map_err(|_| fen_error(InvalidFenReason::InvalidHalfmoveClock))?;
+        }
+
+        if let Some(fullmove_number) = fields.get(5) {
+            board.fullmove_number = match fullmove_number.parse() {
+                Ok(number) if number > 0 => number,
+                _ => return Err(fen_error(InvalidFenReason::InvalidFullmoveNumber)),
+            };
+        }
+
+        board.zobrist_key = board.compute_zobrist_key();
+
+        Ok(board)
+    }
+
+    pub fn to_fen(&self) -> String {
+        let mut fen = String::with_capacity(90);
+
+        for rank in (0..8).rev() {
+            let mut empty_count = 0;
+
+            for file in 0..8 {
+                // Boards built through `from`/`from_fen` are never malformed
+                match self.piece_at(rank, file).unwrap_or(None) {
+                    Some(piece) => {
+                        if empty_count > 0 {
+                            fen += &empty_count.to_string();
+                            empty_count = 0;
+                        }
+
+                        let chr = piece.piece_type.to_char();
+                        fen.push(if piece.player == Player::White {
+                            chr
+                        } else {
+                            chr.to_ascii_lowercase()
+                        });
+                    }
+                    None => empty_count += 1,
+                }
+            }
+
+            if empty_count > 0 {
+                fen += &empty_count.to_string();
+            }
+
+            if rank > 0 {
+                fen.push('/');
+            }
+        }
+
+        fen += match self.next_player {
+            Player::White => " w ",
+            Player::Black => " b ",
+        };
+
+        fen += &self.castling_rights.to_fen();
+        fen.push(' ');
+
+        let en_passant_mask = self.check_en_passant();
+        if en_passant_mask.is_empty() {
+            fen.push('-');
+        } else {
+            // The target square is the one the double-moving pawn skipped over
+            let target = match self.next_player {
+                Player::White => en_passant_mask.shift_up(1),
+                Player::Black => en_passant_mask.shift_down(1),
+            };
+
+            fen += &RankFile::from(target.first_bit_position()).to_string();
+        }
+
+        fen += &format!(" {} {}", self.halfmove_clock, self.fullmove_number);
+
+        fen
+    }
+
+    fn parse_placement(&mut self, placement: &str) -> Result<(), BoardError> {
+        let mut pieces = [BitBoard::empty(); PIECE_COUNT];
+        let mut players = [BitBoard::empty(); PLAYER_COUNT];
+
+        let ranks: Vec<&str> = placement.split('/').collect();
+
+        if ranks.len() != 8 {
+            return Err(fen_error(InvalidFenReason::IncorrectRankCount));
+        }
+
+        for (i, rank_str) in ranks.iter().enumerate() {
+            let rank = (7 - i) as u8;
+            let mut file: u8 = 0;
+
+            for chr in rank_str.chars() {
+                if let Some(skip) = chr.to_digit(10) {
+                    if skip == 0 || skip > 8 {
+                        return Err(fen_error(InvalidFenReason::InvalidPiece { chr }));
+                    }
+
+                    file += skip as u8;
+                } else {
+                    let piece = Piece::from(chr)
+                        .ok_or_else(|| fen_error(InvalidFenReason::InvalidPiece { chr }))?;
+
+                    if file >= 8 {
+                        return Err(fen_error(InvalidFenReason::IncorrectRankLength {
+                            rank: rank + 1,
+                        }));
+                    }
+
+                    let piece_mask = BitBoard::from(BitPosition::from((rank, file)));
+
+                    players[piece.player as usize] |= piece_mask;
+                    pieces[piece.piece_type as usize] |= piece_mask;
+
+                    file += 1;
+                }
+
+                if file > 8 {
+                    break;
+                }
+            }
+
+            if file != 8 {
+                return Err(fen_error(InvalidFenReason::IncorrectRankLength {
+                    rank: rank + 1,
+                }));
+            }
+        }
+
+        self.pieces = pieces;
+        self.players = players;
+
+        Ok(())
+    }
+
+    fn parse_castling_rights(&mut self, castling_rights: &str) -> Result<(), BoardError> {
+        if castling_rights == "-" {
+            return Ok(());
+        }
+
+        for chr in castling_rights.chars() {
+            let right = CastlingRights::from_flag(chr)
+                .ok_or_else(|| fen_error(InvalidFenReason::InvalidCastlingRights))?;
+
+            if self.castling_rights.contains(right) {
+                return Err(fen_error(InvalidFenReason::InvalidCastlingRights));
+            }
+
+            self.castling_rights.grant(right);
+        }
+
+        Ok(())
+    }
+
+    fn parse_en_passant(&mut self, en_passant: &str) -> Result<(), BoardError> {
+        if en_passant == "-" {
+            return Ok(());
+        }
+
+        let target: RankFile = en_passant
+            .parse()
+            .map_err(|_| fen_error(InvalidFenReason::InvalidEnPassant))?;
+
+        // En passant is derived from the previous move, so we reconstruct the
+        //  double move that must have preceded this position.
+        let (expected_rank, direction) = match self.next_player {
+            Player::White => (5, -1),
+            Player::Black => (2, 1),
+        };
+
+        if target.rank() != expected_rank {
+            return Err(fen_error(InvalidFenReason::InvalidEnPassant));
+        }
+
+        let target_position = BitPosition::from(target);
+        let from = target_position.shift(0, -direction);
+        let to = target_position.shift(0, direction);
+
+        let enemy_pawns = self.pieces[PieceType::Pawn as usize]
+            .intersect(self.players[1 - (self.next_player as usize)]);
+
+        if enemy_pawns.intersect(to.into()).is_empty() {
+            return Err(fen_error(InvalidFenReason::InvalidEnPassant));
+        }
+
+        self.prev_move = Some(Move {
+            piece_type: PieceType::Pawn,
+            from: from.into(),
+            to: to.into(),
+            ..Default::default()
+        });
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess::errors::{BoardError, InvalidFenReason};
+    use crate::chess::STARTING_FEN;
+
+    const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+    #[test]
+    fn test_starting_position() {
+        let board = Board::from_fen(STARTING_FEN).unwrap();
+
+        assert_eq!(board.next_player, Player::White);
+        assert_eq!(board.pieces[PieceType::King as usize].count_pieces(), 2);
+        assert_eq!(board.pieces[PieceType::Pawn as usize].count_pieces(), 16);
+        assert_eq!(board.all_pieces().count_pieces(), 32);
+        assert_eq!(
+            board.piece_at(0, 4).unwrap(),
+            Some(Piece {
+                piece_type: PieceType::King,
+                player: Player::White
+            })
+        );
+        assert_eq!(
+            board.piece_at(7, 3).unwrap(),
+            Some(Piece {
+                piece_type: PieceType::Queen,
+                player: Player::Black
+            })
+        );
+        assert_eq!(board.prev_move, None);
+        assert_eq!(board.halfmove_clock, 0);
+        assert_eq!(board.fullmove_number, 1);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let fens = [
+            STARTING_FEN,
+            KIWIPETE,
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+            "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2",
+            "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+            "4k3/8/8/8/8/8/8/4K2R b K - 37 104",
+        ];
+
+        for fen in fens.iter() {
+            assert_eq!(Board::from_fen(fen).unwrap().to_fen(), *fen);
+        }
+    }
+
+    #[test]
+    fn test_optional_counters() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/4K3 b - -").unwrap();
+
+        assert_eq!(board.to_fen(), "4k3/8/8/8/8/8/8/4K3 b - - 0 1");
+    }
+
+    #[test]
+    fn test_en_passant_from_fen() {
+        let board =
+            Board::from_fen("rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2")
+                .unwrap();
+
+        assert_eq!(board.check_en_passant(), BitBoard::from(RankFile::E5));
+    }
+
+    #[test]
+    fn test_grid_board_to_fen() {
+        let board = Board::from(crate::chess::DEFAULT_BOARD, Player::White).unwrap();
+
+        assert_eq!(
+            board.to_fen(),
+            "rnbkqbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBKQBNR w - - 0 1"
+        );
+    }
+
+    #[test]
+    fn test_invalid_fens() {
+        let cases = [
+            ("", InvalidFenReason::IncorrectFieldCount),
+            (
+                "8/8/8/8/8/8/8/8 w - - 0 1 extra",
+                InvalidFenReason::IncorrectFieldCount,
+            ),
+            (
+                "8/8/8/8/8/8/8 w - - 0 1",
+                InvalidFenReason::IncorrectRankCount,
+            ),
+            (
+                "8/8/8/8/8/8/8/7 w - - 0 1",
+                InvalidFenReason::IncorrectRankLength { rank: 1 },
+            ),
+            (
+                "ppppppppp/8/8/8/8/8/8/8 w - - 0 1",
+                InvalidFenReason::IncorrectRankLength { rank: 8 },
+            ),
+            (
+                "8/8/8/8/8/8/8/4X3 w - - 0 1",
+                InvalidFenReason::InvalidPiece { chr: 'X' },
+            ),
+            (
+                "8/8/8/8/8/8/8/8 x - - 0 1",
+                InvalidFenReason::InvalidActivePlayer,
+            ),
+            (
+                "8/8/8/8/8/8/8/8 w KK - 0 1",
+                InvalidFenReason::InvalidCastlingRights,
+            ),
+            (
+                "8/8/8/8/8/8/8/8 w A - 0 1",
+                InvalidFenReason::InvalidCastlingRights,
+            ),
+            (
+                "8/8/8/8/8/8/8/8 w - e3 0 1",
+                InvalidFenReason::InvalidEnPassant,
+            ),
+            (
+                "8/8/8/8/8/8/8/8 w - e6 0 1",
+                InvalidFenReason::InvalidEnPassant,
+            ),
+            (
+                "8/8/8/8/8/8/8/8 w - z9 0 1",
+                InvalidFenReason::InvalidEnPassant,
+            ),
+            (
+                "8/8/8/8/8/8/8/8 w - - -1 1",
+                InvalidFenReason::InvalidHalfmoveClock,
+            ),
+            (
+                "8/8/8/8/8/8/8/8 w - - 0 0",
+                InvalidFenReason::InvalidFullmoveNumber,
+            ),
+        ];
+
+        for (fen, reason) in cases.iter() {
+            assert_eq!(
+                Board::from_fen(fen),
+                Err(BoardError::InvalidFen(*reason)),
+                "Expected {:?} to fail",
+                fen
+            );
+        }
+    }
+}
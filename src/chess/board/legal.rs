@@ -0,0 +1,234 @@
+use super::Board;
+
+use crate::chess::bitboard::ENDS;
+use crate::chess::magic;
+use crate::chess::PIECE_COUNT;
+use crate::chess::{BitBoard, BitPosition, GenType, MoveList, PieceType, RankFile};
+
+impl Board {
+    /// Generates only legal moves by working out the checks and pins on
+    ///  `king` first, instead of playing each move and testing it.  Produces
+    ///  the same moves in the same order as `make_and_test_moves`.
+    pub(super) fn generate_legal_moves(&self, king: BitPosition, gen_type: GenType) -> MoveList {
+        let mut moves = MoveList::new();
+
+        let player_mask = self.players[self.next_player as usize];
+        let enemy_mask = self.enemy_mask();
+        let king_mask = BitBoard::from(king);
+
+        let checkers = self.enemy_attackers(king, self.all_pieces());
+
+        if gen_type == GenType::Evasions && checkers.is_empty() {
+            return moves;
+        }
+
+        let pinned = self.pinned_pieces(king);
+
+        // Promotions count as captures, so pawns moving onto the last rank
+        //  are split off from the rest of their moves
+        let (target_mask, pawn_target_mask) = match gen_type {
+            GenType::Captures => (enemy_mask, enemy_mask.join(ENDS)),
+            GenType::Quiets => (enemy_mask.inverse(), enemy_mask.join(ENDS).inverse()),
+            GenType::Evasions | GenType::All => {
+                (BitBoard::empty().inverse(), BitBoard::empty().inverse())
+            }
+        };
+
+        // The spaces a piece other than the king can move to that deal
+        //  with the current check, if there is one
+        let evasion_mask = match checkers.count_pieces() {
+            0 => BitBoard::empty().inverse(),
+            1 => checkers.join(magic::between(king, checkers.first_bit_position())),
+            _ => BitBoard::empty(),
+        };
+
+        for piece_index in 0..PIECE_COUNT {
+            let piece_type: PieceType = num::FromPrimitive::from_usize(piece_index).unwrap();
+
+            for current_position in self.pieces[piece_index].intersect(player_mask) {
+                let current_position_mask = BitBoard::from(current_position);
+                let from = RankFile::from(current_position);
+
+                let available_moves = self
+                    .find_moves(piece_type, current_position, current_position_mask)
+                    .intersect(if piece_type == PieceType::Pawn {
+                        pawn_target_mask
+                    } else {
+                        target_mask
+                    })
+                    - player_mask;
+
+                if piece_type == PieceType::King {
+                    if checkers.is_empty() && gen_type != GenType::Captures {
+                        self.push_castles(from, &mut moves);
+                    }
+
+                    // The king can't hide behind itself from a slider
+                    let occupancy = self.all_pieces() - king_mask;
+                    let safe_moves = available_moves
+                        .filter(|&next_position| {
+                            self.enemy_attackers(next_position, occupancy).is_empty()
+                        })
+                        .fold(BitBoard::empty(), |acc, next_position| {
+                            acc.join(next_position.into())
+                        });
+
+                    self.push_moves(piece_type, from, safe_moves, &mut moves);
+                    continue;
+                }
+
+                if piece_type == PieceType::Pawn && gen_type != GenType::Quiets {
+                    for next_position in self.find_en_passant_move(current_position) {
+                        if self.is_en_passant_legal(king, current_position, next_position) {
+                            self.push_en_passant(from, next_position.into(), &mut moves);
+                        }
+                    }
+                }
+
+                let mut legal_moves = available_moves.intersect(evasion_mask);
+
+                if !current_position_mask.intersect(pinned).is_empty() {
+                    legal_moves = legal_moves.intersect(magic::line(king, current_position));
+                }
+
+                self.push_moves(piece_type, from, legal_moves, &mut moves);
+            }
+        }
+
+        moves
+    }
+
+    // The enemy pieces that would attack `position` if the board held
+    //  `occupancy` instead of its own pieces
+    fn enemy_attackers(&self, position: BitPosition, occupancy: BitBoard) -> BitBoard {
+        self.attackers_to(position, occupancy)
+            .intersect(self.enemy_mask())
+    }
+
+    // Our pieces that are the only thing standing between `king` and an
+    //  enemy rook, bishop or queen
+    fn pinned_pieces(&self, king: BitPosition) -> BitBoard {
+        self.slider_blockers(king, self.enemy_mask())
+            .intersect(self.players[self.next_player as usize])
+    }
+
+    /// Pieces of either player that are the only thing standing between
+    ///  `king` and one of the rooks, bishops or queens in `attackers`.
+    pub(super) fn slider_blockers(&self, king: BitPosition, attackers: BitBoard) -> BitBoard {
+        let queens = self.pieces[PieceType::Queen as usize];
+
+        // Sliders that would attack the king on an empty board
+        let snipers = magic::rook_attacks(king, BitBoard::empty())
+            .intersect(self.pieces[PieceType::Rook as usize].join(queens))
+            .join(
+                magic::bishop_attacks(king, BitBoard::empty())
+                    .intersect(self.pieces[PieceType::Bishop as usize].join(queens)),
+            )
+            .intersect(attackers);
+
+        snipers.fold(BitBoard::empty(), |blockers, sniper| {
+            let between = magic::between(king, sniper).intersect(self.all_pieces());
+
+            if between.count_pieces() == 1 {
+                blockers.join(between)
+            } else {
+                blockers
+            }
+        })
+    }
+
+    // En passant removes two pawns from the same rank at once, which can
+    //  uncover an attack that no pin accounts for, so it's checked directly
+    fn is_en_passant_legal(
+        &self,
+        king: BitPosition,
+        current_position: BitPosition,
+        next_position: BitPosition,
+    ) -> bool {
+        let captured_mask = BitBoard::from(next_position.shift(0, -self.pawn_direction()));
+
+        let occupancy = (self.all_pieces() - current_position.into() - captured_mask)
+            .join(next_position.into());
+
+        self.enemy_attackers(king, occupancy).is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess::{Move, MoveType};
+
+    fn king_of(board: &Board) -> BitPosition {
+        board.players[board.next_player as usize]
+            .intersect(board.pieces[PieceType::King as usize])
+            .first_bit_position()
+    }
+
+    #[test]
+    fn test_pinned_pieces() {
+        let board = Board::from_fen("4k3/4r3/8/8/1b6/2N5/3PB3/4K2r w - - 0 1").unwrap();
+
+        assert_eq!(
+            board.pinned_pieces(king_of(&board)),
+            BitBoard::from(RankFile::E2)
+        );
+
+        // Two of our pieces in the way means neither is pinned
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/q1NBK3 w - - 0 1").unwrap();
+        assert!(board.pinned_pieces(king_of(&board)).is_empty());
+    }
+
+    #[test]
+    fn test_double_check_only_moves_king() {
+        let board = Board::from_fen("4r2k/8/8/8/8/5n1Q/8/4K3 w - - 0 1").unwrap();
+        let moves = board.generate_legal_moves(king_of(&board), GenType::All);
+
+        assert_eq!(
+            board
+                .enemy_attackers(king_of(&board), board.all_pieces())
+                .count_pieces(),
+            2
+        );
+        assert!(!moves.is_empty());
+        assert!(moves.iter().all(|mv| mv.piece_type == PieceType::King));
+    }
+
+    #[test]
+    fn test_check_evasions() {
+        // Only blocking on e2..e7 or capturing the rook gets out of check
+        let board = Board::from_fen("k3r3/8/8/8/8/8/1R6/4K1N1 w - - 0 1").unwrap();
+        let moves = board.generate_legal_moves(king_of(&board), GenType::All);
+
+        assert!(moves.contains(&Move {
+            piece_type: PieceType::Rook,
+            from: RankFile::B2,
+            to: RankFile::E2,
+            ..Default::default()
+        }));
+        assert!(moves.contains(&Move {
+            piece_type: PieceType::Knight,
+            from: RankFile::G1,
+            to: RankFile::E2,
+            ..Default::default()
+        }));
+        assert!(moves
+            .iter()
+            .filter(|mv| mv.piece_type != PieceType::King)
+            .all(|mv| mv.to.file() == 4));
+    }
+
+    #[test]
+    fn test_en_passant_discovered_check() {
+        // Taking en passant would leave the king on the same rank as the rook
+        let board = Board::from_fen("8/8/8/K2pP2r/8/8/8/7k w - d6 0 1").unwrap();
+        let moves = board.generate_legal_moves(king_of(&board), GenType::All);
+
+        assert!(moves.iter().all(|mv| mv.move_type != MoveType::EnPassant));
+
+        let board = Board::from_fen("8/8/8/3pP3/8/8/8/K6k w - d6 0 1").unwrap();
+        let moves = board.generate_legal_moves(king_of(&board), GenType::All);
+
+        assert!(moves.iter().any(|mv| mv.move_type == MoveType::EnPassant));
+    }
+}
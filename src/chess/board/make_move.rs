@@ -0,0 +1,298 @@
+use super::zobrist;
+use super::Board;
+
+use crate::chess::{BitBoard, BitPosition, CastlingRights, Move, MoveType, PieceType, Player};
+
+/// Everything `make_move` throws away that `unmake_move` needs to restore
+///  the board exactly.  Callers searching several plies deep keep these on
+///  a stack alongside the moves that produced them.
+#[derive(PartialEq, Debug, Clone)]
+pub struct UndoInfo {
+    captured_piece: Option<PieceType>,
+    castling_rights: CastlingRights,
+    prev_move: Option<Move>,
+    halfmove_clock: u32,
+    fullmove_number: u32,
+    zobrist_key: u64,
+}
+
+impl Board {
+    /// Plays `mv` in place.  The move must be pseudo-legal for this position,
+    ///  e.g. taken from the `prev_move` of a generated child.
+    pub fn make_move(&mut self, mv: &Move) -> UndoInfo {
+        let player = self.next_player;
+        let mut undo = UndoInfo {
+            captured_piece: None,
+            castling_rights: self.castling_rights,
+            prev_move: None,
+            halfmove_clock: self.halfmove_clock,
+            fullmove_number: self.fullmove_number,
+            zobrist_key: self.zobrist_key,
+        };
+
+        let from_mask = BitBoard::from(mv.from);
+        let to_mask = BitBoard::from(mv.to);
+
+        debug_assert!(
+            !self.pieces[mv.piece_type as usize]
+                .intersect(self.players[player as usize])
+                .intersect(from_mask)
+                .is_empty(),
+            "Expected to move piece, {:?} {:?}, but it wasn't on {:?}",
+            player,
+            mv.piece_type,
+            mv.from
+        );
+
+        self.zobrist_key ^= self.state_key() ^ zobrist::side_key();
+        undo.prev_move = self.prev_move.take();
+
+        let capture_mask = match mv.move_type {
+            MoveType::Castling { .. } => BitBoard::empty(),
+            _ => BitBoard::from(self.capture_position(mv)).intersect(self.enemy_mask()),
+        };
+
+        if !capture_mask.is_empty() {
+            let captured_piece = self.remove_piece(capture_mask);
+
+            self.zobrist_key ^= zobrist::piece_key(
+                player.opponent(),
+                captured_piece,
+                capture_mask.first_bit_position(),
+            );
+            undo.captured_piece = Some(captured_piece);
+        }
+
+        let placed_piece = Board::placed_piece(mv);
+        self.toggle_piece(player, mv.piece_type, mv.from.into());
+        self.toggle_piece(player, placed_piece, mv.to.into());
+
+        if let MoveType::Castling { is_queenside } = mv.move_type {
+            let (rook_from, rook_to) = Board::castling_rook_squares(player, is_queenside);
+
+            self.toggle_piece(player, PieceType::Rook, rook_from);
+            self.toggle_piece(player, PieceType::Rook, rook_to);
+        }
+
+        self.castling_rights.revoke_touched(from_mask.join(to_mask));
+
+        if mv.piece_type == PieceType::Pawn || undo.captured_piece.is_some() {
+            self.halfmove_clock = 0;
+        } else {
+            self.halfmove_clock += 1;
+        }
+
+        if player == Player::Black {
+            self.fullmove_number += 1;
+        }
+
+        self.prev_move = Some(mv.clone());
+        self.next_player = player.opponent();
+        self.zobrist_key ^= self.state_key();
+
+        debug_assert_eq!(
+            self.zobrist_key,
+            self.compute_zobrist_key(),
+            "Incremental zobrist key diverged from the position"
+        );
+
+        undo
+    }
+
+    /// Reverts `mv`, which must be the last move made on this board.
+    pub fn unmake_move(&mut self, mv: &Move, undo: UndoInfo) {
+        let player = self.next_player.opponent();
+
+        if let MoveType::Castling { is_queenside } = mv.move_type {
+            let (rook_from, rook_to) = Board::castling_rook_squares(player, is_queenside);
+
+            self.toggle_piece(player, PieceType::Rook, rook_to);
+            self.toggle_piece(player, PieceType::Rook, rook_from);
+        }
+
+        self.toggle_piece(player, Board::placed_piece(mv), mv.to.into());
+        self.toggle_piece(player, mv.piece_type, mv.from.into());
+
+        self.next_player = player;
+
+        if let Some(captured_piece) = undo.captured_piece {
+            let capture_mask = BitBoard::from(self.capture_position(mv));
+
+            self.pieces[captured_piece as usize] |= capture_mask;
+            self.players[player.opponent() as usize] |= capture_mask;
+        }
+
+        self.castling_rights = undo.castling_rights;
+        self.prev_move = undo.prev_move;
+        self.halfmove_clock = undo.halfmove_clock;
+        self.fullmove_number = undo.fullmove_number;
+        self.zobrist_key = undo.zobrist_key;
+
+        debug_assert_eq!(
+            self.zobrist_key,
+            self.compute_zobrist_key(),
+            "Unmaking {} did not restore the position",
+            mv
+        );
+    }
+
+    // The square of the piece `mv` would capture, which is not the
+    //  destination for en passant.  Must be called while `mv`'s player is
+    //  to move.
+    fn capture_position(&self, mv: &Move) -> BitPosition {
+        let to = BitPosition::from(mv.to);
+
+        if mv.move_type == MoveType::EnPassant {
+            to.shift(0, -self.pawn_direction())
+        } else {
+            to
+        }
+    }
+
+    fn placed_piece(mv: &Move) -> PieceType {
+        match mv.move_type {
+            MoveType::Promotion { promoted_to } => promoted_to,
+            _ => mv.piece_type,
+        }
+    }
+
+    fn castling_rook_squares(player: Player, is_queenside: bool) -> (BitPosition, BitPosition) {
+        let rank = match player {
+            Player::White => 0,
+            Player::Black => 7,
+        };
+
+        if is_queenside {
+            (BitPosition::from((rank, 0)), BitPosition::from((rank, 3)))
+        } else {
+            (BitPosition::from((rank, 7)), BitPosition::from((rank, 5)))
+        }
+    }
+
+    // Adds the piece if the square is empty, or removes it if it's there
+    fn toggle_piece(&mut self, player: Player, piece_type: PieceType, position: BitPosition) {
+        let mask = BitBoard::from(position);
+
+        self.pieces[piece_type as usize] = self.pieces[piece_type as usize].toggle(mask);
+        self.players[player as usize] = self.players[player as usize].toggle(mask);
+        self.zobrist_key ^= zobrist::piece_key(player, piece_type, position);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess::{DEFAULT_BOARD, STARTING_FEN};
+    use crate::fixtures::*;
+
+    fn fixture_boards() -> Vec<Board> {
+        let grids = [
+            DEFAULT_BOARD,
+            WHITE_PAWN_TEST,
+            BLACK_PAWN_TEST,
+            WHITE_PROMOTION_TEST,
+            BLACK_PROMOTION_TEST,
+            WHITE_ROOK_TEST,
+            BLACK_ROOK_TEST,
+            WHITE_KNIGHT_TEST,
+            BLACK_KNIGHT_TEST,
+            WHITE_BISHOP_TEST,
+            BLACK_BISHOP_TEST,
+            WHITE_QUEEN_TEST,
+            BLACK_QUEEN_TEST,
+            WHITE_KING_TEST,
+            BLACK_KING_TEST,
+            CASTLING_TEST_UNOBSTRUCTED,
+            CASTLING_TEST_OBSTRUCTED,
+            CASTLING_TEST_FROM_CHECK,
+            CASTLING_TEST_THROUGH_CHECK,
+        ];
+
+        let mut boards = vec![];
+
+        for grid in grids.iter() {
+            for &player in [Player::White, Player::Black].iter() {
+                boards.push(Board::from(grid, player).unwrap());
+            }
+        }
+
+        for (grid, player, prev_move) in [
+            (WHITE_PAWN_TEST, Player::White, WHITE_EN_PASSANT),
+            (BLACK_PAWN_TEST, Player::Black, BLACK_EN_PASSANT),
+        ]
+        .iter()
+        {
+            let mut board = Board::from(grid, *player).unwrap();
+            board.prev_move = Some(prev_move.clone());
+            board.zobrist_key = board.compute_zobrist_key();
+            boards.push(board);
+        }
+
+        for fen in [
+            STARTING_FEN,
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+        ]
+        .iter()
+        {
+            boards.push(Board::from_fen(fen).unwrap());
+        }
+
+        boards
+    }
+
+    #[test]
+    fn test_make_matches_generated_children() {
+        for board in fixture_boards() {
+            for child in board.generate_moves() {
+                let mv = child.prev_move.clone().unwrap();
+
+                let mut made = board.clone();
+                made.make_move(&mv);
+
+                assert_eq!(made, child, "make_move({}) differed from generation", mv);
+            }
+        }
+    }
+
+    #[test]
+    fn test_make_unmake_is_identity() {
+        for board in fixture_boards() {
+            let mut made = board.clone();
+
+            for child in board.generate_moves() {
+                let mv = child.prev_move.unwrap();
+
+                let undo = made.make_move(&mv);
+                made.unmake_move(&mv, undo);
+
+                assert_eq!(made, board, "unmake_move({}) did not restore the board", mv);
+            }
+        }
+    }
+
+    #[test]
+    fn test_undo_stack() {
+        let original = Board::from_fen(STARTING_FEN).unwrap();
+        let mut board = original.clone();
+        let mut stack = vec![];
+
+        // Follow the first generated move a few plies deep, then unwind
+        for _ in 0..6 {
+            let mv = board.generate_moves().next().unwrap().prev_move.unwrap();
+            let undo = board.make_move(&mv);
+
+            stack.push((mv, undo));
+        }
+
+        let deepest = board.clone();
+        assert_ne!(deepest, original);
+
+        while let Some((mv, undo)) = stack.pop() {
+            board.unmake_move(&mv, undo);
+        }
+
+        assert_eq!(board, original);
+    }
+}
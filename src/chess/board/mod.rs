@@ -2,23 +2,41 @@ use std::fmt;
 use std::fmt::{Debug, Display, Formatter};
 
 mod attacks;
+mod check;
+mod fen;
+mod legal;
+mod make_move;
+mod moves;
 mod pawn;
+mod perft;
 mod pieces;
+mod san;
+mod see;
+mod uci;
+mod zobrist;
 
-use crate::chess::bitboard::ENDS;
+use crate::chess::bitboard::{CASTLE_CHECK, ENDS, KINGSIDE_CASTLE, QUEENSIDE_CASTLE};
 use crate::chess::errors::{BoardError, InvalidStringReason};
 use crate::chess::{
-    BitBoard, BitPosition, Move, MoveGenerator, MoveType, Piece, PieceType, Player, RankFile,
+    BitBoard, BitPosition, CastlingRights, Move, MoveGenerator, MoveType, Piece, PieceType, Player,
+    RankFile,
 };
 use crate::chess::{PIECE_COUNT, PLAYER_COUNT};
 
+pub use self::make_move::UndoInfo;
+
 #[derive(PartialEq, Clone)]
 pub struct Board {
     pub pieces: [BitBoard; PIECE_COUNT],
     pub players: [BitBoard; PLAYER_COUNT],
-    pub unmoved_pieces: BitBoard,
+    pub castling_rights: CastlingRights,
     pub prev_move: Option<Move>,
     pub next_player: Player,
+    /// Plies since the last capture or pawn move, for the fifty-move rule
+    pub halfmove_clock: u32,
+    /// Starts at 1 and is incremented after every black move
+    pub fullmove_number: u32,
+    pub zobrist_key: u64,
 }
 
 impl Default for Board {
@@ -28,7 +46,10 @@ impl Default for Board {
             players: [BitBoard::empty(); PLAYER_COUNT],
             prev_move: None,
             next_player: Player::White,
-            unmoved_pieces: BitBoard::empty().inverse(),
+            castling_rights: CastlingRights::none(),
+            halfmove_clock: 0,
+            fullmove_number: 1,
+            zobrist_key: 0,
         }
     }
 }
@@ -123,18 +144,28 @@ impl Board {
             }
         }
 
-        Ok(Board {
+        // The grid format has no castling field, so we assume any king and
+        //  rook still on their home squares have never moved.
+        let castling_rights = CastlingRights::from_placement(&pieces, &players);
+
+        let mut board = Board {
             pieces,
             players,
             next_player: player,
+            castling_rights,
             ..Default::default()
-        })
+        };
+        board.zobrist_key = board.compute_zobrist_key();
+
+        Ok(board)
     }
 
     pub fn generate_moves(&self) -> MoveGenerator {
         MoveGenerator::new(self.clone(), self.next_player)
     }
 
+    /// Builds the board after moving a piece.  Pawns reaching the last rank
+    ///  always become queens here, use `apply` for underpromotions.
     pub fn move_piece(
         &self,
         piece: PieceType,
@@ -149,7 +180,12 @@ impl Board {
         let piece_index = piece as usize;
         let player_index = self.next_player as usize;
 
-        board.unmoved_pieces -= current_position_mask.join(next_position_mask);
+        board.zobrist_key ^= self.state_key() ^ zobrist::side_key();
+        board.zobrist_key ^= zobrist::piece_key(self.next_player, piece, current_position);
+
+        board
+            .castling_rights
+            .revoke_touched(current_position_mask.join(next_position_mask));
 
         debug_assert!(
             !board.pieces[piece_index]
@@ -176,7 +212,13 @@ impl Board {
             // self.slide_move_sanity_check(&board, next_position_mask);
         } else {
             // self.capture_sanity_check(&board, capture_mask);
-            board.remove_piece(capture_mask);
+            let captured_piece = board.remove_piece(capture_mask);
+
+            board.zobrist_key ^= zobrist::piece_key(
+                self.next_player.opponent(),
+                captured_piece,
+                capture_mask.first_bit_position(),
+            );
         }
 
         board.players[player_index] |= next_position_mask;
@@ -205,17 +247,97 @@ impl Board {
         };
 
         board.pieces[next_piece as usize] |= next_position_mask;
+        board.zobrist_key ^= zobrist::piece_key(self.next_player, next_piece, next_position);
 
         debug_assert!(self.prev_move != board.prev_move);
 
+        if piece == PieceType::Pawn || !capture_mask.is_empty() {
+            board.halfmove_clock = 0;
+        } else {
+            board.halfmove_clock += 1;
+        }
+
+        if self.next_player == Player::Black {
+            board.fullmove_number += 1;
+        }
+
         board.next_player = match self.next_player {
             Player::White => Player::Black,
             Player::Black => Player::White,
         };
 
+        board.zobrist_key ^= board.state_key();
+
+        debug_assert_eq!(
+            board.zobrist_key,
+            board.compute_zobrist_key(),
+            "Incremental zobrist key diverged from the position"
+        );
+
         board
     }
 
+    /// Whether a king of `player`'s on `square` is the one that castles.
+    pub(crate) fn is_castling_king(&self, player: Player, square: RankFile) -> bool {
+        let king_home = match player {
+            Player::White => RankFile::E1,
+            Player::Black => RankFile::E8,
+        };
+
+        // Test boards may have several kings, only the one at home can castle
+        square == king_home
+    }
+
+    /// Whether the player to move may castle to the given side right now:
+    ///  they still have the right, nothing stands between king and rook and
+    ///  the king doesn't start in, pass through or land on an attacked square.
+    pub fn can_castle(&self, is_queenside: bool) -> bool {
+        let player = self.next_player;
+
+        if !self.castling_rights.has(player, is_queenside) {
+            return false;
+        }
+
+        let player_mask = self.players[player as usize];
+        let (king_mask, rook_mask, mut spaces) = if is_queenside {
+            (
+                BitBoard::from(RankFile::E1),
+                BitBoard::from(RankFile::A1),
+                QUEENSIDE_CASTLE,
+            )
+        } else {
+            (
+                BitBoard::from(RankFile::E1),
+                BitBoard::from(RankFile::H1),
+                KINGSIDE_CASTLE,
+            )
+        };
+
+        let (king_mask, rook_mask) = if player == Player::Black {
+            spaces = spaces.shift_up(7);
+            (king_mask.shift_up(7), rook_mask.shift_up(7))
+        } else {
+            (king_mask, rook_mask)
+        };
+
+        // The rights should guarantee these, but hand-built boards may not
+        if self.pieces[PieceType::King as usize]
+            .intersect(player_mask)
+            .intersect(king_mask)
+            .is_empty()
+            || self.pieces[PieceType::Rook as usize]
+                .intersect(player_mask)
+                .intersect(rook_mask)
+                .is_empty()
+        {
+            return false;
+        }
+
+        spaces.intersect(self.all_pieces()).is_empty()
+            && !self.is_attacked(player, king_mask)
+            && !self.is_attacked(player, spaces.intersect(CASTLE_CHECK))
+    }
+
     pub fn perform_castle(&mut self, is_queenside: bool) -> Board {
         let king_position: BitPosition = match self.next_player {
             Player::White => RankFile::E1,
@@ -265,6 +387,7 @@ impl Board {
             BitBoard::empty(),
         );
         board.next_player = self.next_player;
+        board.zobrist_key ^= zobrist::side_key();
 
         let next_king_position = next_king_mask.first_bit_position();
         let mut board = board.move_piece(
@@ -277,20 +400,38 @@ impl Board {
         );
 
         board.prev_move.as_mut().map(|m| {
-            m.is_capture = true;
             m.move_type = MoveType::Castling { is_queenside };
         });
 
+        // Castling is built from two piece moves, but only counts as one
+        board.halfmove_clock = self.halfmove_clock + 1;
+        board.fullmove_number = self.fullmove_number;
+        if self.next_player == Player::Black {
+            board.fullmove_number += 1;
+        }
+
         board
     }
 
-    fn remove_piece(&mut self, next_position_mask: BitBoard) {
+    pub fn is_fifty_move_draw(&self) -> bool {
+        self.halfmove_clock >= 100
+    }
+
+    fn remove_piece(&mut self, next_position_mask: BitBoard) -> PieceType {
+        let mut removed_piece = PieceType::Pawn;
+
         for i in 0..PIECE_COUNT {
+            if !self.pieces[i].intersect(next_position_mask).is_empty() {
+                removed_piece = num::FromPrimitive::from_usize(i).unwrap();
+            }
+
             self.pieces[i] -= next_position_mask;
         }
 
         // And the previous player
         self.players[1 - (self.next_player as usize)] -= next_position_mask;
+
+        removed_piece
     }
 }
 
@@ -512,4 +653,81 @@ mod tests {
         assert_eq!(board.piece_at(0, 5), Err(BoardError::MalformedBoard));
     }
 
+    #[test]
+    fn test_move_counters() {
+        let board = Board::from_fen("r3k2r/8/8/8/3p4/8/4P1N1/R3K2R w KQkq - 7 12").unwrap();
+
+        let knight = board.move_piece(
+            PieceType::Knight,
+            RankFile::G2.into(),
+            RankFile::G2.into(),
+            RankFile::F4.into(),
+            RankFile::F4.into(),
+            BitBoard::empty(),
+        );
+        assert_eq!(knight.halfmove_clock, 8);
+        assert_eq!(knight.fullmove_number, 12);
+
+        let pawn = board.move_piece(
+            PieceType::Pawn,
+            RankFile::E2.into(),
+            RankFile::E2.into(),
+            RankFile::E4.into(),
+            RankFile::E4.into(),
+            BitBoard::empty(),
+        );
+        assert_eq!(pawn.halfmove_clock, 0);
+        assert_eq!(pawn.fullmove_number, 12);
+
+        let en_passant = pawn
+            .generate_en_passant_board(RankFile::D4.into(), RankFile::D4.into())
+            .unwrap();
+        assert_eq!(en_passant.halfmove_clock, 0);
+        assert_eq!(en_passant.fullmove_number, 13);
+
+        let mut board = board;
+        let castled = board.perform_castle(false);
+        assert_eq!(castled.halfmove_clock, 8);
+        assert_eq!(castled.fullmove_number, 12);
+
+        let mut board = Board::from_fen("r3k2r/8/8/8/3p4/8/4P1N1/R3K2R b KQkq - 7 12").unwrap();
+        let castled = board.perform_castle(true);
+        assert_eq!(castled.halfmove_clock, 8);
+        assert_eq!(castled.fullmove_number, 13);
+        assert_eq!(
+            castled.to_fen(),
+            "2kr3r/8/8/8/3p4/8/4P1N1/R3K2R w KQ - 8 13"
+        );
+    }
+
+    #[test]
+    fn test_castling_rights_revoked() {
+        let board = Board::from_fen("r3k2r/8/8/8/8/8/6N1/R3K2R b KQkq - 0 1").unwrap();
+
+        // Capturing the rook on its home square takes away that right
+        let rook = board.move_piece(
+            PieceType::Rook,
+            RankFile::H8.into(),
+            RankFile::H8.into(),
+            RankFile::H1.into(),
+            RankFile::H1.into(),
+            RankFile::H1.into(),
+        );
+        assert_eq!(rook.castling_rights.to_fen(), "Qq");
+
+        let king = board.move_piece(
+            PieceType::King,
+            RankFile::E8.into(),
+            RankFile::E8.into(),
+            RankFile::E7.into(),
+            RankFile::E7.into(),
+            BitBoard::empty(),
+        );
+        assert_eq!(king.castling_rights.to_fen(), "KQ");
+
+        let mut board = Board::from_fen("r3k2r/8/8/8/8/8/6N1/R3K2R w KQkq - 0 1").unwrap();
+        let castled = board.perform_castle(true);
+        assert_eq!(castled.castling_rights.to_fen(), "kq");
+    }
+
 }
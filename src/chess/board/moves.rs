@@ -0,0 +1,336 @@
+use super::Board;
+
+use crate::chess::bitboard::ENDS;
+use crate::chess::{BitBoard, GenType, Move, MoveList, MoveType, PieceType, Player, RankFile};
+use crate::chess::{PIECE_COUNT, PROMOTION_PIECES};
+
+impl Board {
+    /// Every legal move for the player to move, in the same order
+    ///  `generate_moves` produces the child boards.
+    pub fn legal_moves(&self) -> MoveList {
+        self.legal_moves_of(GenType::All)
+    }
+
+    /// The legal moves of one kind, in the same relative order as
+    ///  `legal_moves`.
+    pub fn legal_moves_of(&self, gen_type: GenType) -> MoveList {
+        let mut moves = self.legal_moves_unannotated(gen_type);
+
+        for mv in moves.iter_mut() {
+            self.mark_check(mv);
+        }
+
+        moves
+    }
+
+    /// `legal_moves_of` without working out `threatens_king` and
+    ///  `is_checkmate`, which are left `false`.  Much cheaper when only
+    ///  the moves themselves are needed, as when counting them in perft.
+    pub fn legal_moves_unannotated(&self, gen_type: GenType) -> MoveList {
+        let king_mask = self.players[self.next_player as usize]
+            .intersect(self.pieces[PieceType::King as usize]);
+
+        // Checks and pins only make sense with exactly one king, which some
+        //  test boards don't have
+        if king_mask.count_pieces() != 1 {
+            return self.make_and_test_moves(gen_type);
+        }
+
+        let moves = self.generate_legal_moves(king_mask.first_bit_position(), gen_type);
+
+        debug_assert_eq!(
+            moves,
+            self.make_and_test_moves(gen_type),
+            "Legal move generation disagrees with make-and-test for\n{}",
+            self
+        );
+
+        moves
+    }
+
+    // Plays every pseudo-legal move and keeps the ones that don't leave our
+    //  king attacked.  Slow, but hard to get wrong.
+    fn make_and_test_moves(&self, gen_type: GenType) -> MoveList {
+        if gen_type == GenType::Evasions && !self.is_king_attacked(self.next_player) {
+            return MoveList::new();
+        }
+
+        let mut moves = self.pseudo_legal_moves();
+        let mut scratch = self.clone();
+
+        moves.retain(|mv| {
+            if !gen_type.includes(mv) {
+                return false;
+            }
+
+            let undo = scratch.make_move(mv);
+            let is_legal = !scratch.is_king_attacked(self.next_player);
+            scratch.unmake_move(mv, undo);
+
+            is_legal
+        });
+
+        moves
+    }
+
+    /// Every move that follows the piece movement rules, including ones
+    ///  that leave the current player's king attacked.
+    pub fn pseudo_legal_moves(&self) -> MoveList {
+        let mut moves = MoveList::new();
+
+        let player_mask = self.players[self.next_player as usize];
+
+        for piece_index in 0..PIECE_COUNT {
+            let piece_type: PieceType = num::FromPrimitive::from_usize(piece_index).unwrap();
+
+            for current_position in self.pieces[piece_index].intersect(player_mask) {
+                let current_position_mask = BitBoard::from(current_position);
+                let from = RankFile::from(current_position);
+
+                if piece_type == PieceType::Pawn {
+                    for next_position in self.find_en_passant_move(current_position) {
+                        self.push_en_passant(from, next_position.into(), &mut moves);
+                    }
+                }
+
+                if piece_type == PieceType::King {
+                    self.push_castles(from, &mut moves);
+                }
+
+                let available_moves =
+                    self.find_moves(piece_type, current_position, current_position_mask)
+                        - player_mask;
+
+                self.push_moves(piece_type, from, available_moves, &mut moves);
+            }
+        }
+
+        moves
+    }
+
+    /// Builds the board after `mv`, leaving this one untouched.
+    pub fn apply(&self, mv: &Move) -> Board {
+        let mut board = self.clone();
+        board.make_move(mv);
+
+        board
+    }
+
+    pub fn is_king_attacked(&self, player: Player) -> bool {
+        // Test boards may have more than one king, so every one is checked
+        let king_mask =
+            self.players[player as usize].intersect(self.pieces[PieceType::King as usize]);
+
+        king_mask
+            .into_iter()
+            .any(|king_position| self.is_attacked(player, king_position.into()))
+    }
+
+    // Pushes a move to each of `targets`, expanding pawns reaching the last
+    //  rank into every promotion
+    pub(super) fn push_moves(
+        &self,
+        piece_type: PieceType,
+        from: RankFile,
+        targets: BitBoard,
+        moves: &mut MoveList,
+    ) {
+        let enemy_mask = self.enemy_mask();
+
+        for next_position in targets {
+            let next_position_mask = BitBoard::from(next_position);
+
+            let mv = Move {
+                piece_type,
+                from,
+                to: next_position.into(),
+                is_capture: !next_position_mask.intersect(enemy_mask).is_empty(),
+                ..Default::default()
+            };
+
+            if piece_type == PieceType::Pawn && !next_position_mask.intersect(ENDS).is_empty() {
+                for &promoted_to in PROMOTION_PIECES.iter() {
+                    moves.push(Move {
+                        move_type: MoveType::Promotion { promoted_to },
+                        ..mv.clone()
+                    });
+                }
+            } else {
+                moves.push(mv);
+            }
+        }
+    }
+
+    pub(super) fn push_en_passant(&self, from: RankFile, to: RankFile, moves: &mut MoveList) {
+        moves.push(Move {
+            piece_type: PieceType::Pawn,
+            from,
+            to,
+            move_type: MoveType::EnPassant,
+            is_capture: true,
+            ..Default::default()
+        });
+    }
+
+    pub(super) fn push_castles(&self, from: RankFile, moves: &mut MoveList) {
+        if !self.is_castling_king(self.next_player, from) {
+            return;
+        }
+
+        let (queenside_to, kingside_to) = match self.next_player {
+            Player::White => (RankFile::C1, RankFile::G1),
+            Player::Black => (RankFile::C8, RankFile::G8),
+        };
+
+        for &(is_queenside, to) in [(true, queenside_to), (false, kingside_to)].iter() {
+            if self.can_castle(is_queenside) {
+                moves.push(Move {
+                    piece_type: PieceType::King,
+                    from,
+                    to,
+                    move_type: MoveType::Castling { is_queenside },
+                    ..Default::default()
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess::STARTING_FEN;
+    use crate::fixtures::*;
+
+    fn check_against_generator(board: &Board) {
+        let moves = board.legal_moves();
+        let children: Vec<Board> = board.generate_moves().collect();
+
+        assert_eq!(moves.len(), children.len(), "{}", board);
+
+        for (mv, child) in moves.iter().zip(children.iter()) {
+            assert_eq!(Some(mv), child.prev_move.as_ref());
+            assert_eq!(board.apply(mv), *child);
+        }
+    }
+
+    fn test_boards() -> Vec<Board> {
+        let mut boards = vec![];
+
+        for grid in [
+            WHITE_PAWN_TEST,
+            BLACK_PAWN_TEST,
+            WHITE_PROMOTION_TEST,
+            BLACK_PROMOTION_TEST,
+            WHITE_ROOK_TEST,
+            BLACK_ROOK_TEST,
+            WHITE_KNIGHT_TEST,
+            BLACK_KNIGHT_TEST,
+            WHITE_BISHOP_TEST,
+            BLACK_BISHOP_TEST,
+            WHITE_QUEEN_TEST,
+            BLACK_QUEEN_TEST,
+            WHITE_KING_TEST,
+            BLACK_KING_TEST,
+            CASTLING_TEST_UNOBSTRUCTED,
+            CASTLING_TEST_OBSTRUCTED,
+            CASTLING_TEST_FROM_CHECK,
+            CASTLING_TEST_THROUGH_CHECK,
+        ]
+        .iter()
+        {
+            for &player in [Player::White, Player::Black].iter() {
+                boards.push(Board::from(grid, player).unwrap());
+            }
+        }
+
+        let mut board = Board::from(WHITE_PAWN_TEST, Player::White).unwrap();
+        board.prev_move = Some(WHITE_EN_PASSANT);
+        board.zobrist_key = board.compute_zobrist_key();
+        boards.push(board);
+
+        for fen in [
+            STARTING_FEN,
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+            "4r2k/8/8/8/8/5n1Q/8/4K3 w - - 0 1",
+        ]
+        .iter()
+        {
+            boards.push(Board::from_fen(fen).unwrap());
+        }
+
+        boards
+    }
+
+    #[test]
+    fn test_matches_board_generator() {
+        for board in test_boards() {
+            check_against_generator(&board);
+        }
+    }
+
+    #[test]
+    fn test_captures_and_quiets_make_all() {
+        for board in test_boards() {
+            let moves = board.legal_moves();
+            let captures = board.legal_moves_of(GenType::Captures);
+            let quiets = board.legal_moves_of(GenType::Quiets);
+
+            let (expected_captures, expected_quiets): (Vec<Move>, Vec<Move>) = moves
+                .iter()
+                .cloned()
+                .partition(|mv| GenType::Captures.includes(mv));
+
+            assert_eq!(&captures[..], &expected_captures[..], "{}", board);
+            assert_eq!(&quiets[..], &expected_quiets[..], "{}", board);
+            assert_eq!(captures.len() + quiets.len(), moves.len());
+        }
+    }
+
+    #[test]
+    fn test_evasions() {
+        for board in test_boards() {
+            let evasions = board.legal_moves_of(GenType::Evasions);
+
+            if board.is_king_attacked(board.next_player) {
+                assert_eq!(evasions, board.legal_moves(), "{}", board);
+            } else {
+                assert!(evasions.is_empty(), "{}", board);
+            }
+        }
+
+        let board = Board::from_fen("4r2k/8/8/8/8/5n1Q/8/4K3 w - - 0 1").unwrap();
+        assert!(!board.legal_moves_of(GenType::Evasions).is_empty());
+    }
+
+    #[test]
+    fn test_starting_moves() {
+        let board = Board::from_fen(STARTING_FEN).unwrap();
+        let moves = board.legal_moves();
+
+        assert_eq!(moves.len(), 20);
+        assert!(moves.iter().all(|mv| !mv.is_capture));
+        assert!(moves.contains(&Move {
+            piece_type: PieceType::Knight,
+            from: RankFile::G1,
+            to: RankFile::F3,
+            ..Default::default()
+        }));
+    }
+
+    #[test]
+    fn test_pinned_piece_cannot_move() {
+        let board = Board::from_fen("4k3/4r3/8/8/8/8/4B3/4K3 w - - 0 1").unwrap();
+
+        assert!(board
+            .legal_moves()
+            .iter()
+            .all(|mv| mv.piece_type != PieceType::Bishop));
+        assert!(!board
+            .pseudo_legal_moves()
+            .iter()
+            .all(|mv| mv.piece_type != PieceType::Bishop));
+    }
+}
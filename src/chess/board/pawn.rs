@@ -1,6 +1,7 @@
 use super::Board;
 
 use crate::chess::bitboard::{FILE_A, FILE_B, FILE_G, FILE_H};
+use crate::chess::leapers;
 use crate::chess::{BitBoard, BitPosition, PieceType, Player, MoveType};
 
 impl Board {
@@ -44,24 +45,10 @@ impl Board {
     fn diagonals(&self, current_position: BitPosition) -> BitBoard {
         covered_by!("Pawn::diagonals -> White");
         covered_by!("Pawn::diagonals -> Black");
-        let direction = self.pawn_direction();
-
-        let left_diagonal = if !current_position.is_leftmost() {
-            BitBoard::from(current_position.shift(-1, direction))
-        } else {
-            BitBoard::empty()
-        };
-
-        let right_diagonal = if !current_position.is_rightmost() {
-            BitBoard::from(current_position.shift(1, direction))
-        } else {
-            BitBoard::empty()
-        };
-
-        left_diagonal.join(right_diagonal)
+        leapers::pawn_attacks(self.next_player, current_position)
     }
 
-    fn pawn_direction(&self) -> i32 {
+    pub(super) fn pawn_direction(&self) -> i32 {
         match self.next_player {
             Player::White => 1,
             Player::Black => -1,
@@ -103,15 +90,13 @@ impl Board {
         possible_double_move - self.all_pieces()
     }
 
-    pub fn generate_en_passant_board(
-        &self,
-        current_position: BitPosition,
-        current_position_mask: BitBoard,
-    ) -> Option<Board> {
+    /// The square the pawn at `current_position` would move to by capturing
+    ///  en passant, or an empty board if it can't.
+    pub fn find_en_passant_move(&self, current_position: BitPosition) -> BitBoard {
         let en_passant_mask = self.check_en_passant();
 
         if en_passant_mask.is_empty() {
-            return None;
+            return BitBoard::empty();
         }
 
         debug_assert!(
@@ -119,10 +104,13 @@ impl Board {
             "There can only be a single en passant capture per piece possible."
         );
 
-        let next_position_mask = en_passant_mask
-            .shift_down(1)
-            .join(en_passant_mask.shift_up(1))
-            .intersect(self.diagonals(current_position));
+        // The pawn lands behind the one it captures, from the capturer's side
+        let next_position_mask = BitBoard::from(
+            en_passant_mask
+                .first_bit_position()
+                .shift(0, self.pawn_direction()),
+        )
+        .intersect(self.diagonals(current_position));
 
         debug_assert!(
             next_position_mask != en_passant_mask,
@@ -130,7 +118,18 @@ impl Board {
              pawn being captured. Not of the position being moved to."
         );
 
+        next_position_mask
+    }
+
+    pub fn generate_en_passant_board(
+        &self,
+        current_position: BitPosition,
+        current_position_mask: BitBoard,
+    ) -> Option<Board> {
+        let next_position_mask = self.find_en_passant_move(current_position);
+
         if !next_position_mask.is_empty() {
+            let en_passant_mask = self.check_en_passant();
             let new_move = next_position_mask.first_bit_position();
 
             let mut board = self.move_piece(
@@ -217,6 +216,21 @@ mod tests {
         assert_eq!(board.check_en_passant(), RankFile::D4.into());
     }
 
+    #[test]
+    fn test_en_passant_only_from_beside() {
+        let board = Board::from_fen("4k3/8/8/1p6/8/P7/8/4K3 w - b6 0 1").unwrap();
+        assert_eq!(
+            board.find_en_passant_move(RankFile::A3.into()),
+            BitBoard::empty()
+        );
+
+        let board = Board::from_fen("4k3/8/8/Pp6/8/8/8/4K3 w - b6 0 1").unwrap();
+        assert_eq!(
+            board.find_en_passant_move(RankFile::A5.into()),
+            RankFile::B6.into()
+        );
+    }
+
     #[test]
     fn test_single_moves_white() {
         covers!("Pawn::available_single_moves -> White");
@@ -0,0 +1,88 @@
+use super::Board;
+
+use crate::chess::{GenType, Move};
+
+impl Board {
+    /// Counts the leaf nodes of the legal move tree `depth` plies deep.
+    ///  Comparing against published counts is the quickest way to catch
+    ///  move generation bugs that only show up between pieces.
+    pub fn perft(&self, depth: u32) -> u64 {
+        let mut board = self.clone();
+
+        board.perft_in_place(depth)
+    }
+
+    /// `perft` split up by the first move, for narrowing down which move
+    ///  a wrong count comes from.
+    pub fn divide(&self, depth: u32) -> Vec<(Move, u64)> {
+        if depth == 0 {
+            return vec![];
+        }
+
+        let mut board = self.clone();
+
+        self.legal_moves_unannotated(GenType::All)
+            .into_iter()
+            .map(|mv| {
+                let undo = board.make_move(&mv);
+                let nodes = board.perft_in_place(depth - 1);
+                board.unmake_move(&mv, undo);
+
+                (mv, nodes)
+            })
+            .collect()
+    }
+
+    fn perft_in_place(&mut self, depth: u32) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+
+        let moves = self.legal_moves_unannotated(GenType::All);
+
+        // The leaves don't need to be played out to be counted
+        if depth == 1 {
+            return moves.len() as u64;
+        }
+
+        moves
+            .iter()
+            .map(|mv| {
+                let undo = self.make_move(mv);
+                let nodes = self.perft_in_place(depth - 1);
+                self.unmake_move(mv, undo);
+
+                nodes
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess::STARTING_FEN;
+
+    #[test]
+    fn test_shallow_counts() {
+        let board = Board::from_fen(STARTING_FEN).unwrap();
+
+        assert_eq!(board.perft(0), 1);
+        assert_eq!(board.perft(1), 20);
+        assert_eq!(board.perft(2), 400);
+        assert_eq!(board.perft(3), 8902);
+    }
+
+    #[test]
+    fn test_divide_sums_to_perft() {
+        let board = Board::from_fen(STARTING_FEN).unwrap();
+        let divided = board.divide(3);
+
+        assert_eq!(divided.len(), 20);
+        assert_eq!(
+            divided.iter().map(|&(_, nodes)| nodes).sum::<u64>(),
+            board.perft(3)
+        );
+        assert!(board.divide(0).is_empty());
+    }
+}
@@ -0,0 +1,365 @@
+use super::Board;
+
+use crate::chess::errors::MoveParseError;
+use crate::chess::{BitPosition, GenType, Move, MoveType, PieceType, RankFile};
+
+impl Board {
+    /// Writes `mv`, a legal move for the player to move, in Standard
+    ///  Algebraic Notation, e.g. `e4`, `Nbd7`, `exd6`, `e8=Q+` or `O-O-O#`.
+    pub fn to_san(&self, mv: &Move) -> String {
+        let mut san = String::new();
+
+        if let MoveType::Castling { is_queenside } = mv.move_type {
+            san.push_str(if is_queenside { "O-O-O" } else { "O-O" });
+        } else {
+            if mv.piece_type == PieceType::Pawn {
+                if mv.is_capture {
+                    san.push(mv.from.file_char());
+                }
+            } else {
+                san.push(mv.piece_type.to_char());
+                san.push_str(&self.disambiguation(mv));
+            }
+
+            if mv.is_capture {
+                san.push('x');
+            }
+
+            san.push_str(&mv.to.to_string());
+
+            if let MoveType::Promotion { promoted_to } = mv.move_type {
+                san.push('=');
+                san.push(promoted_to.to_char());
+            }
+        }
+
+        // Moves built by hand may not have their check flags filled in
+        let mut mv = mv.clone();
+        self.mark_check(&mut mv);
+
+        if mv.is_checkmate {
+            san.push('#');
+        } else if mv.threatens_king {
+            san.push('+');
+        }
+
+        san
+    }
+
+    /// Finds the legal move that `text` describes in Standard Algebraic
+    ///  Notation.  Check marks, annotations like `!?` and an `e.p.` suffix
+    ///  are optional, and castling may be written with zeros.
+    pub fn parse_san(&self, text: &str) -> Result<Move, MoveParseError> {
+        let pattern = SanPattern::parse(text).ok_or_else(|| MoveParseError::Malformed {
+            text: text.to_owned(),
+        })?;
+
+        let mut matches = self
+            .legal_moves()
+            .into_iter()
+            .filter(|mv| pattern.matches(mv));
+
+        match (matches.next(), matches.next()) {
+            (Some(mv), None) => Ok(mv),
+            (None, _) => Err(MoveParseError::Illegal {
+                text: text.to_owned(),
+            }),
+            _ => Err(MoveParseError::Ambiguous {
+                text: text.to_owned(),
+            }),
+        }
+    }
+
+    // Just enough of the starting square to tell `mv` apart from any other
+    //  piece of the same kind that could move to the same space
+    fn disambiguation(&self, mv: &Move) -> String {
+        let rivals: Vec<RankFile> = self
+            .legal_moves_unannotated(GenType::All)
+            .iter()
+            .filter(|other| {
+                other.piece_type == mv.piece_type && other.to == mv.to && other.from != mv.from
+            })
+            .map(|other| other.from)
+            .collect();
+
+        if rivals.is_empty() {
+            String::new()
+        } else if rivals.iter().all(|rival| rival.file() != mv.from.file()) {
+            mv.from.file_char().to_string()
+        } else if rivals.iter().all(|rival| rival.rank() != mv.from.rank()) {
+            mv.from.rank_char().to_string()
+        } else {
+            mv.from.to_string()
+        }
+    }
+}
+
+// What a SAN string says about the move it stands for.  Anything left
+//  out, like the starting square, matches every move.
+enum SanPattern {
+    Castling {
+        is_queenside: bool,
+    },
+    Move {
+        piece_type: PieceType,
+        from_file: Option<u8>,
+        from_rank: Option<u8>,
+        to: RankFile,
+        is_capture: bool,
+        promoted_to: Option<PieceType>,
+    },
+}
+
+impl SanPattern {
+    fn parse(text: &str) -> Option<SanPattern> {
+        let mut text = text.trim().trim_end_matches(|chr| "+#!?".contains(chr));
+
+        if text.ends_with("e.p.") {
+            text = text[..text.len() - 4].trim_end();
+        }
+
+        match text {
+            "O-O" | "0-0" => {
+                return Some(SanPattern::Castling {
+                    is_queenside: false,
+                })
+            }
+            "O-O-O" | "0-0-0" => return Some(SanPattern::Castling { is_queenside: true }),
+            _ => {}
+        }
+
+        let mut chars: Vec<char> = text.chars().collect();
+
+        let piece_type = match chars.first() {
+            Some(&chr) if "NBRQK".contains(chr) => {
+                chars.remove(0);
+                PieceType::from(chr)?
+            }
+            _ => PieceType::Pawn,
+        };
+
+        let promoted_to = match chars.last() {
+            Some(&chr) if "NBRQ".contains(chr) => {
+                chars.pop();
+
+                if chars.last() == Some(&'=') {
+                    chars.pop();
+                }
+
+                Some(PieceType::from(chr)?)
+            }
+            _ => None,
+        };
+
+        let rank = RankFile::parse_rank(chars.pop()?)?;
+        let file = RankFile::parse_file(chars.pop()?)?;
+
+        let is_capture = chars.last() == Some(&'x');
+
+        if is_capture {
+            chars.pop();
+        }
+
+        // Whatever is left has to be the start of the move's square
+        let (from_file, from_rank) = match chars.as_slice() {
+            [] => (None, None),
+            [chr] => match RankFile::parse_file(*chr) {
+                Some(from_file) => (Some(from_file), None),
+                None => (None, Some(RankFile::parse_rank(*chr)?)),
+            },
+            [file_chr, rank_chr] => (
+                Some(RankFile::parse_file(*file_chr)?),
+                Some(RankFile::parse_rank(*rank_chr)?),
+            ),
+            _ => return None,
+        };
+
+        Some(SanPattern::Move {
+            piece_type,
+            from_file,
+            from_rank,
+            to: BitPosition::from((rank, file)).into(),
+            is_capture,
+            promoted_to,
+        })
+    }
+
+    fn matches(&self, mv: &Move) -> bool {
+        match *self {
+            SanPattern::Castling { is_queenside } => {
+                mv.move_type == MoveType::Castling { is_queenside }
+            }
+            SanPattern::Move {
+                piece_type,
+                from_file,
+                from_rank,
+                to,
+                is_capture,
+                promoted_to,
+            } => {
+                let move_promoted_to = match mv.move_type {
+                    MoveType::Promotion { promoted_to } => Some(promoted_to),
+                    _ => None,
+                };
+
+                mv.piece_type == piece_type
+                    && mv.to == to
+                    && from_file.iter().all(|&file| mv.from.file() == file)
+                    && from_rank.iter().all(|&rank| mv.from.rank() == rank)
+                    && (mv.is_capture || !is_capture)
+                    && move_promoted_to == promoted_to
+                    && !matches!(mv.move_type, MoveType::Castling { .. })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess::STARTING_FEN;
+
+    fn sans(fen: &str) -> Vec<String> {
+        let board = Board::from_fen(fen).unwrap();
+
+        board
+            .legal_moves()
+            .iter()
+            .map(|mv| board.to_san(mv))
+            .collect()
+    }
+
+    #[test]
+    fn test_pieces_and_pawns() {
+        let moves = sans(STARTING_FEN);
+
+        assert_eq!(moves.len(), 20);
+        assert!(moves.contains(&"e4".to_owned()));
+        assert!(moves.contains(&"a3".to_owned()));
+        assert!(moves.contains(&"Nf3".to_owned()));
+        assert!(moves.contains(&"Na3".to_owned()));
+    }
+
+    #[test]
+    fn test_captures_and_promotions() {
+        let moves = sans("3r2k1/4P3/8/3pP3/8/8/8/4K3 w - d6 0 1");
+
+        assert!(moves.contains(&"exd6".to_owned()));
+        assert!(moves.contains(&"exd8=Q+".to_owned()));
+        assert!(moves.contains(&"exd8=N".to_owned()));
+        assert!(moves.contains(&"e8=R+".to_owned()));
+        assert!(moves.contains(&"e8=B".to_owned()));
+    }
+
+    #[test]
+    fn test_castling_and_checks() {
+        let moves = sans("r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1");
+
+        assert!(moves.contains(&"O-O".to_owned()));
+        assert!(moves.contains(&"O-O-O".to_owned()));
+        assert!(moves.contains(&"Rxa1+".to_owned()));
+
+        let moves = sans("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1");
+        assert!(moves.contains(&"Ra8#".to_owned()));
+    }
+
+    #[test]
+    fn test_disambiguation() {
+        // Rooks on the same rank, then on the same file
+        let moves = sans("4k3/8/8/8/8/8/8/R4RK1 w - - 0 1");
+        assert!(moves.contains(&"Rad1".to_owned()));
+        assert!(moves.contains(&"Rfd1".to_owned()));
+        assert!(moves.contains(&"Ra2".to_owned()));
+
+        let moves = sans("4k3/8/8/R7/8/8/8/R3K3 w - - 0 1");
+        assert!(moves.contains(&"R5a3".to_owned()));
+        assert!(moves.contains(&"R1a3".to_owned()));
+
+        // Only the file and rank together pick out the queen on h4
+        let moves = sans("1k6/8/8/8/4Q2Q/8/8/K6Q w - - 0 1");
+        assert!(moves.contains(&"Qh4e1".to_owned()));
+        assert!(moves.contains(&"Qee1".to_owned()));
+        assert!(moves.contains(&"Q1e1".to_owned()));
+
+        // The knight on f3 is pinned, so the other one needs no hint
+        let moves = sans("4k3/8/8/3b4/8/5N2/8/1N5K w - - 0 1");
+        assert!(moves.contains(&"Nd2".to_owned()));
+    }
+
+    #[test]
+    fn test_parse_round_trips() {
+        for fen in [
+            STARTING_FEN,
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            "3r2k1/4P3/8/3pP3/8/8/8/4K3 w - d6 0 1",
+            "1k6/8/8/8/4Q2Q/8/8/K6Q w - - 0 1",
+        ]
+        .iter()
+        {
+            let board = Board::from_fen(fen).unwrap();
+
+            for mv in board.legal_moves().iter() {
+                assert_eq!(board.parse_san(&board.to_san(mv)).as_ref(), Ok(mv));
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_variants() {
+        let board = Board::from_fen("r3k2r/8/8/3pP3/8/8/8/R3K2R w KQkq d6 0 1").unwrap();
+
+        let castle = board.parse_san("0-0-0").unwrap();
+        assert_eq!(castle.move_type, MoveType::Castling { is_queenside: true });
+        assert_eq!(board.parse_san("O-O-O"), Ok(castle));
+
+        let en_passant = board.parse_san("exd6 e.p.").unwrap();
+        assert_eq!(en_passant.move_type, MoveType::EnPassant);
+        assert_eq!(board.parse_san("exd6"), Ok(en_passant.clone()));
+        assert_eq!(board.parse_san("exd6e.p."), Ok(en_passant));
+
+        // Check marks and annotations are optional, and needn't be right
+        let rook_check = board.parse_san("Rxa8+").unwrap();
+        assert_eq!(board.parse_san("Rxa8"), Ok(rook_check.clone()));
+        assert_eq!(board.parse_san("Rxa8!?"), Ok(rook_check));
+
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/1N2KN2 w - - 0 1").unwrap();
+        assert_eq!(board.parse_san("Nbd2").unwrap().from, RankFile::B1);
+        assert_eq!(board.parse_san("Nfd2").unwrap().from, RankFile::F1);
+        assert_eq!(board.parse_san("Nf1d2").unwrap().from, RankFile::F1);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/4P3/1N2KN2 w - - 0 1").unwrap();
+
+        for &text in [
+            "", "Zd2", "e9", "Kd2x", "Nxyd2", "O-O-O-O", "Nb1c3d2", "e2-e4",
+        ]
+        .iter()
+        {
+            assert_eq!(
+                board.parse_san(text),
+                Err(MoveParseError::Malformed {
+                    text: text.to_owned()
+                })
+            );
+        }
+
+        for &text in ["e5", "Nd3", "exd3", "O-O", "e8=Q"].iter() {
+            assert_eq!(
+                board.parse_san(text),
+                Err(MoveParseError::Illegal {
+                    text: text.to_owned()
+                })
+            );
+        }
+
+        assert_eq!(
+            board.parse_san("Nd2"),
+            Err(MoveParseError::Ambiguous {
+                text: "Nd2".to_owned()
+            })
+        );
+    }
+}
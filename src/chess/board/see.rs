@@ -0,0 +1,213 @@
+use super::Board;
+
+use crate::chess::{BitBoard, BitPosition, Move, MoveType, PieceType, PieceValues, Player};
+
+// Sized for every piece on the board taking a turn on the same square
+const MAX_EXCHANGES: usize = 32;
+
+impl Board {
+    /// Static exchange evaluation of `mv` with the default `PieceValues`.
+    ///  See `see_with`.
+    pub fn see(&self, mv: &Move) -> i32 {
+        self.see_with(mv, &PieceValues::default())
+    }
+
+    /// How much material the player making `mv` comes out ahead once both
+    ///  sides have finished recapturing on its target, always with their
+    ///  least valuable attacker and stopping as soon as it stops paying off.
+    ///  Negative for captures that lose material.
+    pub fn see_with(&self, mv: &Move, values: &PieceValues) -> i32 {
+        let from_mask = BitBoard::from(mv.from);
+        let to = BitPosition::from(mv.to);
+
+        let mut player = if self.players[Player::White as usize]
+            .intersect(from_mask)
+            .is_empty()
+        {
+            Player::White
+        } else {
+            Player::Black
+        };
+
+        let mut occupancy = self.all_pieces() - from_mask;
+        let mut gains = [0; MAX_EXCHANGES];
+
+        gains[0] = if mv.move_type == MoveType::EnPassant {
+            occupancy -= BitPosition::from((mv.from.rank(), mv.to.file())).into();
+            values.pawn
+        } else {
+            self.piece_type_on(to.into())
+                .map_or(0, |piece_type| values.value(piece_type))
+        };
+
+        // What the next capture on the target would win
+        let mut on_target = values.value(mv.piece_type);
+
+        if let MoveType::Promotion { promoted_to } = mv.move_type {
+            gains[0] += values.value(promoted_to) - values.pawn;
+            on_target = values.value(promoted_to);
+        }
+
+        let mut depth = 0;
+
+        // Each capture takes its piece out of `occupancy`, which lets any
+        //  slider lined up behind it join in
+        while let Some((piece_type, position)) = self.least_valuable_attacker(
+            self.attackers_to(to, occupancy)
+                .intersect(self.players[player as usize]),
+            values,
+        ) {
+            depth += 1;
+            gains[depth] = on_target - gains[depth - 1];
+
+            on_target = values.value(piece_type);
+            occupancy -= position.into();
+            player = player.opponent();
+        }
+
+        // Either side can stop recapturing when carrying on would lose more
+        while depth > 0 {
+            gains[depth - 1] = -(-gains[depth - 1]).max(gains[depth]);
+            depth -= 1;
+        }
+
+        gains[0]
+    }
+
+    fn least_valuable_attacker(
+        &self,
+        attackers: BitBoard,
+        values: &PieceValues,
+    ) -> Option<(PieceType, BitPosition)> {
+        attackers
+            .filter_map(|position| {
+                self.piece_type_on(position.into())
+                    .map(|piece_type| (piece_type, position))
+            })
+            .min_by_key(|&(piece_type, _)| values.value(piece_type))
+    }
+
+    fn piece_type_on(&self, mask: BitBoard) -> Option<PieceType> {
+        (0..self.pieces.len())
+            .find(|&piece_index| !self.pieces[piece_index].intersect(mask).is_empty())
+            .and_then(num::FromPrimitive::from_usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess::RankFile;
+
+    fn capture(piece_type: PieceType, from: RankFile, to: RankFile) -> Move {
+        Move {
+            piece_type,
+            from,
+            to,
+            is_capture: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_see_undefended() {
+        let board = Board::from_fen("1k1r4/1pp4p/p7/4p3/8/P5P1/1PP4P/2K1R3 w - - 0 1").unwrap();
+
+        assert_eq!(
+            board.see(&capture(PieceType::Rook, RankFile::E1, RankFile::E5)),
+            100
+        );
+    }
+
+    #[test]
+    fn test_see_x_rays() {
+        // The queen behind the rook and the rook behind the bishop both join in
+        let board =
+            Board::from_fen("1k1r3q/1ppn3p/p4b2/4p3/8/P2N2P1/1PP1R1BP/2K1Q3 w - - 0 1").unwrap();
+
+        assert_eq!(
+            board.see(&capture(PieceType::Knight, RankFile::D3, RankFile::E5)),
+            -200
+        );
+    }
+
+    #[test]
+    fn test_see_stops_recapturing() {
+        // The rooks trade off, leaving whoever started a pawn up
+        let board = Board::from_fen("3r2k1/3r4/8/3p4/2P5/8/3R4/3RK3 w - - 0 1").unwrap();
+
+        assert_eq!(
+            board.see(&capture(PieceType::Pawn, RankFile::C4, RankFile::D5)),
+            100
+        );
+        assert_eq!(
+            board.see(&capture(PieceType::Rook, RankFile::D2, RankFile::D5)),
+            100
+        );
+
+        // A pawn guarding the target makes taking with a rook lose the rook
+        let board = Board::from_fen("4k3/2p5/3n4/8/8/8/8/3RK3 w - - 0 1").unwrap();
+
+        assert_eq!(
+            board.see(&capture(PieceType::Rook, RankFile::D1, RankFile::D6)),
+            -200
+        );
+    }
+
+    #[test]
+    fn test_see_king_cant_recapture_defended() {
+        let board = Board::from_fen("4k3/4p3/8/8/8/8/4Q3/4R1K1 w - - 0 1").unwrap();
+
+        assert_eq!(
+            board.see(&capture(PieceType::Queen, RankFile::E2, RankFile::E7)),
+            100
+        );
+    }
+
+    #[test]
+    fn test_see_en_passant_and_promotion() {
+        let board = Board::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+        let mv = Move {
+            piece_type: PieceType::Pawn,
+            from: RankFile::E5,
+            to: RankFile::D6,
+            move_type: MoveType::EnPassant,
+            is_capture: true,
+            ..Default::default()
+        };
+
+        assert_eq!(board.see(&mv), 100);
+
+        let board = Board::from_fen("1r2k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let mv = Move {
+            piece_type: PieceType::Pawn,
+            from: RankFile::A7,
+            to: RankFile::A8,
+            move_type: MoveType::Promotion {
+                promoted_to: PieceType::Queen,
+            },
+            ..Default::default()
+        };
+
+        // The queen is lost to the rook straight away
+        assert_eq!(board.see(&mv), -100);
+    }
+
+    #[test]
+    fn test_see_with_values() {
+        let board = Board::from_fen("4k3/8/3p4/4p3/8/8/8/4RK2 w - - 0 1").unwrap();
+        let mv = capture(PieceType::Rook, RankFile::E1, RankFile::E5);
+
+        assert_eq!(board.see(&mv), -400);
+        assert_eq!(
+            board.see_with(
+                &mv,
+                &PieceValues {
+                    rook: 50,
+                    ..Default::default()
+                }
+            ),
+            50
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use super::Board;
+
+use crate::chess::errors::MoveParseError;
+use crate::chess::{Move, MoveType, PieceType, RankFile};
+
+impl Board {
+    /// Finds the legal move that `text` describes in the long algebraic
+    ///  notation UCI uses, e.g. `e2e4`, `e7e8q` or `e1g1` for castling.
+    pub fn parse_uci_move(&self, text: &str) -> Result<Move, MoveParseError> {
+        let malformed = || MoveParseError::Malformed {
+            text: text.to_owned(),
+        };
+
+        if !text.is_ascii() || (text.len() != 4 && text.len() != 5) {
+            return Err(malformed());
+        }
+
+        let from: RankFile = text[0..2].parse().map_err(|_| malformed())?;
+        let to: RankFile = text[2..4].parse().map_err(|_| malformed())?;
+
+        let promoted_to = match text[4..].chars().next() {
+            Some(chr) if "qrbn".contains(chr) => PieceType::from(chr),
+            Some(_) => return Err(malformed()),
+            None => None,
+        };
+
+        self.legal_moves()
+            .into_iter()
+            .find(|mv| {
+                let move_promoted_to = match mv.move_type {
+                    MoveType::Promotion { promoted_to } => Some(promoted_to),
+                    _ => None,
+                };
+
+                mv.from == from && mv.to == to && move_promoted_to == promoted_to
+            })
+            .ok_or_else(|| MoveParseError::Illegal {
+                text: text.to_owned(),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess::STARTING_FEN;
+
+    #[test]
+    fn test_uci_round_trips() {
+        for fen in [
+            STARTING_FEN,
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            "3r2k1/4P3/8/3pP3/8/8/8/4K3 w - d6 0 1",
+        ]
+        .iter()
+        {
+            let board = Board::from_fen(fen).unwrap();
+
+            for mv in board.legal_moves().iter() {
+                assert_eq!(board.parse_uci_move(&mv.to_uci()).as_ref(), Ok(mv));
+            }
+        }
+    }
+
+    #[test]
+    fn test_uci_moves() {
+        let board = Board::from_fen("r3k3/1P6/8/8/8/8/8/R3K2R w KQq - 0 1").unwrap();
+
+        let castle = board.parse_uci_move("e1g1").unwrap();
+        assert_eq!(
+            castle.move_type,
+            MoveType::Castling {
+                is_queenside: false
+            }
+        );
+        assert_eq!(castle.to_uci(), "e1g1");
+
+        let promotion = board.parse_uci_move("b7a8n").unwrap();
+        assert_eq!(
+            promotion.move_type,
+            MoveType::Promotion {
+                promoted_to: PieceType::Knight
+            }
+        );
+        assert!(promotion.is_capture);
+        assert_eq!(promotion.to_uci(), "b7a8n");
+
+        for &text in ["", "e1", "e1g", "e1g1qq", "e1g9", "E1G1", "b7a8k", "b7a8Q"].iter() {
+            assert_eq!(
+                board.parse_uci_move(text),
+                Err(MoveParseError::Malformed {
+                    text: text.to_owned()
+                })
+            );
+        }
+
+        // Promotions need a piece, and only pawns reaching the end get one
+        for &text in ["b7a8", "a1a3q", "e1e3", "a1h8"].iter() {
+            assert_eq!(
+                board.parse_uci_move(text),
+                Err(MoveParseError::Illegal {
+                    text: text.to_owned()
+                })
+            );
+        }
+    }
+}
@@ -0,0 +1,235 @@
+use std::hash::{Hash, Hasher};
+
+use super::Board;
+
+use crate::chess::random::next_random;
+use crate::chess::{BitPosition, PieceType, Player, RankFile};
+use crate::chess::{PIECE_COUNT, PLAYER_COUNT};
+
+struct ZobristKeys {
+    pieces: [[[u64; 64]; PIECE_COUNT]; PLAYER_COUNT],
+    black_to_move: u64,
+    castling_rights: [u64; 16],
+    en_passant_file: [u64; 8],
+}
+
+lazy_static! {
+    static ref KEYS: ZobristKeys = {
+        let mut state = 0x2545_f491_4f6c_dd1d;
+
+        let mut keys = ZobristKeys {
+            pieces: [[[0; 64]; PIECE_COUNT]; PLAYER_COUNT],
+            black_to_move: next_random(&mut state),
+            castling_rights: [0; 16],
+            en_passant_file: [0; 8],
+        };
+
+        for player in keys.pieces.iter_mut() {
+            for piece in player.iter_mut() {
+                for square in piece.iter_mut() {
+                    *square = next_random(&mut state);
+                }
+            }
+        }
+
+        // Index 0 (no rights) stays 0 so an empty board hashes to 0
+        for rights in keys.castling_rights.iter_mut().skip(1) {
+            *rights = next_random(&mut state);
+        }
+
+        for file in keys.en_passant_file.iter_mut() {
+            *file = next_random(&mut state);
+        }
+
+        keys
+    };
+}
+
+pub(super) fn piece_key(player: Player, piece_type: PieceType, position: BitPosition) -> u64 {
+    KEYS.pieces[player as usize][piece_type as usize][position.right_index as usize]
+}
+
+pub(super) fn side_key() -> u64 {
+    KEYS.black_to_move
+}
+
+impl Board {
+    /// Hashes the position from scratch.  Moves keep `zobrist_key` up to date
+    ///  incrementally, so this is only needed after editing the board by hand.
+    pub fn compute_zobrist_key(&self) -> u64 {
+        let mut key = 0;
+
+        for &player in [Player::Black, Player::White].iter() {
+            for piece_index in 0..PIECE_COUNT {
+                let piece_type = num::FromPrimitive::from_usize(piece_index).unwrap();
+                let mask = self.pieces[piece_index].intersect(self.players[player as usize]);
+
+                for position in mask {
+                    key ^= piece_key(player, piece_type, position);
+                }
+            }
+        }
+
+        if self.next_player == Player::Black {
+            key ^= side_key();
+        }
+
+        key ^ self.state_key()
+    }
+
+    /// The part of the key for castling rights and en passant, which are
+    ///  cheaper to swap out wholesale than to track individually.
+    pub(super) fn state_key(&self) -> u64 {
+        let mut key = KEYS.castling_rights[self.castling_rights.index()];
+
+        if let Some(file) = self.en_passant_file() {
+            key ^= KEYS.en_passant_file[file as usize];
+        }
+
+        key
+    }
+
+    /// Only hashes the en passant file if a pawn can actually take it, so
+    ///  otherwise identical positions still repeat.
+    fn en_passant_file(&self) -> Option<u8> {
+        let en_passant_mask = self.check_en_passant();
+
+        if en_passant_mask.is_empty() {
+            return None;
+        }
+
+        let capturing_pawns = en_passant_mask
+            .shift_left(1)
+            .join(en_passant_mask.shift_right(1))
+            .intersect(self.pieces[PieceType::Pawn as usize])
+            .intersect(self.players[self.next_player as usize]);
+
+        if capturing_pawns.is_empty() {
+            None
+        } else {
+            Some(RankFile::from(en_passant_mask.first_bit_position()).file())
+        }
+    }
+}
+
+impl Hash for Board {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.zobrist_key.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess::{BitBoard, STARTING_FEN};
+
+    #[test]
+    fn test_keys_are_unique() {
+        let mut keys: Vec<u64> = KEYS
+            .pieces
+            .iter()
+            .flat_map(|player| player.iter())
+            .flat_map(|piece| piece.iter())
+            .cloned()
+            .chain(KEYS.castling_rights.iter().skip(1).cloned())
+            .chain(KEYS.en_passant_file.iter().cloned())
+            .collect();
+        keys.push(KEYS.black_to_move);
+
+        let count = keys.len();
+        keys.sort();
+        keys.dedup();
+
+        assert_eq!(keys.len(), count);
+    }
+
+    #[test]
+    fn test_empty_board() {
+        assert_eq!(Board::default().compute_zobrist_key(), 0);
+        assert_eq!(
+            Board::from_fen("8/8/8/8/8/8/8/8 w - - 0 1")
+                .unwrap()
+                .zobrist_key,
+            0
+        );
+    }
+
+    #[test]
+    fn test_key_covers_state() {
+        let board = Board::from_fen(STARTING_FEN).unwrap();
+        let black =
+            Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR b KQkq - 0 1").unwrap();
+        let no_castling =
+            Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w Kkq - 0 1").unwrap();
+
+        assert_eq!(board.zobrist_key, board.compute_zobrist_key());
+        assert_ne!(board.zobrist_key, black.zobrist_key);
+        assert_ne!(board.zobrist_key, no_castling.zobrist_key);
+        assert_ne!(black.zobrist_key, no_castling.zobrist_key);
+    }
+
+    #[test]
+    fn test_en_passant_only_when_capturable() {
+        let capturable = Board::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+        let not_capturable = Board::from_fen("4k3/8/8/3p3P/8/8/8/4K3 w - d6 0 1").unwrap();
+
+        assert_eq!(capturable.en_passant_file(), Some(3));
+        assert_eq!(not_capturable.en_passant_file(), None);
+
+        assert_ne!(
+            capturable.zobrist_key,
+            Board::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - - 0 1")
+                .unwrap()
+                .zobrist_key
+        );
+        assert_eq!(
+            not_capturable.zobrist_key,
+            Board::from_fen("4k3/8/8/3p3P/8/8/8/4K3 w - - 0 1")
+                .unwrap()
+                .zobrist_key
+        );
+    }
+
+    #[test]
+    fn test_transpositions_match() {
+        let board = Board::from_fen(STARTING_FEN).unwrap();
+
+        let play = |board: &Board, moves: &[(PieceType, RankFile, RankFile)]| {
+            moves
+                .iter()
+                .fold(board.clone(), |board, &(piece, from, to)| {
+                    board.move_piece(
+                        piece,
+                        from.into(),
+                        from.into(),
+                        to.into(),
+                        to.into(),
+                        BitBoard::empty(),
+                    )
+                })
+        };
+
+        let knights_first = play(
+            &board,
+            &[
+                (PieceType::Knight, RankFile::G1, RankFile::F3),
+                (PieceType::Knight, RankFile::G8, RankFile::F6),
+                (PieceType::Knight, RankFile::B1, RankFile::C3),
+            ],
+        );
+        let other_knight_first = play(
+            &board,
+            &[
+                (PieceType::Knight, RankFile::B1, RankFile::C3),
+                (PieceType::Knight, RankFile::G8, RankFile::F6),
+                (PieceType::Knight, RankFile::G1, RankFile::F3),
+            ],
+        );
+
+        assert_eq!(knights_first.zobrist_key, other_knight_first.zobrist_key);
+        assert_eq!(
+            knights_first.zobrist_key,
+            knights_first.compute_zobrist_key()
+        );
+    }
+}
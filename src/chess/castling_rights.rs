@@ -0,0 +1,209 @@
+use crate::chess::{BitBoard, PieceType, Player, RankFile};
+use crate::chess::{PIECE_COUNT, PLAYER_COUNT};
+
+const WHITE_KINGSIDE: u8 = 0b0001;
+const WHITE_QUEENSIDE: u8 = 0b0010;
+const BLACK_KINGSIDE: u8 = 0b0100;
+const BLACK_QUEENSIDE: u8 = 0b1000;
+
+// (right, FEN flag, king square, rook square)
+const CASTLING_SQUARES: [(u8, char, RankFile, RankFile); 4] = [
+    (WHITE_KINGSIDE, 'K', RankFile::E1, RankFile::H1),
+    (WHITE_QUEENSIDE, 'Q', RankFile::E1, RankFile::A1),
+    (BLACK_KINGSIDE, 'k', RankFile::E8, RankFile::H8),
+    (BLACK_QUEENSIDE, 'q', RankFile::E8, RankFile::A8),
+];
+
+/// The KQkq castling flags.  A right is only lost, never regained, once
+///  the king or the corresponding rook leaves (or is captured on) its
+///  home square.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct CastlingRights {
+    rights: u8,
+}
+
+impl Default for CastlingRights {
+    fn default() -> Self {
+        CastlingRights::none()
+    }
+}
+
+impl CastlingRights {
+    pub fn none() -> Self {
+        CastlingRights { rights: 0 }
+    }
+
+    pub fn all() -> Self {
+        CastlingRights {
+            rights: WHITE_KINGSIDE | WHITE_QUEENSIDE | BLACK_KINGSIDE | BLACK_QUEENSIDE,
+        }
+    }
+
+    pub fn from_flag(flag: char) -> Option<Self> {
+        CASTLING_SQUARES
+            .iter()
+            .find(|&&(_, chr, _, _)| chr == flag)
+            .map(|&(rights, _, _, _)| CastlingRights { rights })
+    }
+
+    /// Grants every right whose king and rook are standing on their home
+    ///  squares.
+    pub fn from_placement(
+        pieces: &[BitBoard; PIECE_COUNT],
+        players: &[BitBoard; PLAYER_COUNT],
+    ) -> Self {
+        let rights = CASTLING_SQUARES
+            .iter()
+            .filter(|&&(_, flag, king, rook)| {
+                let player_mask = players[Player::from(flag) as usize];
+
+                !pieces[PieceType::King as usize]
+                    .intersect(player_mask)
+                    .intersect(king.into())
+                    .is_empty()
+                    && !pieces[PieceType::Rook as usize]
+                        .intersect(player_mask)
+                        .intersect(rook.into())
+                        .is_empty()
+            })
+            .fold(0, |acc, &(right, _, _, _)| acc | right);
+
+        CastlingRights { rights }
+    }
+
+    fn right_for(player: Player, is_queenside: bool) -> u8 {
+        match (player, is_queenside) {
+            (Player::White, false) => WHITE_KINGSIDE,
+            (Player::White, true) => WHITE_QUEENSIDE,
+            (Player::Black, false) => BLACK_KINGSIDE,
+            (Player::Black, true) => BLACK_QUEENSIDE,
+        }
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.rights == 0
+    }
+
+    pub fn has(self, player: Player, is_queenside: bool) -> bool {
+        self.rights & CastlingRights::right_for(player, is_queenside) != 0
+    }
+
+    pub fn contains(self, other: CastlingRights) -> bool {
+        self.rights & other.rights == other.rights
+    }
+
+    pub fn grant(&mut self, other: CastlingRights) {
+        self.rights |= other.rights;
+    }
+
+    pub fn revoke(&mut self, player: Player, is_queenside: bool) {
+        self.rights &= !CastlingRights::right_for(player, is_queenside);
+    }
+
+    /// Revokes every right whose king or rook home square is in `mask`.
+    ///  Passing the from/to squares of every move keeps the rights up to
+    ///  date, including when a rook is captured before it ever moves.
+    pub fn revoke_touched(&mut self, mask: BitBoard) {
+        for &(right, _, king, rook) in CASTLING_SQUARES.iter() {
+            if !mask
+                .intersect(BitBoard::from(king).join(rook.into()))
+                .is_empty()
+            {
+                self.rights &= !right;
+            }
+        }
+    }
+
+    /// The home squares of the rooks that may still castle for `player`.
+    pub fn rook_squares(self, player: Player) -> BitBoard {
+        CASTLING_SQUARES
+            .iter()
+            .filter(|&&(right, flag, _, _)| {
+                Player::from(flag) == player && self.rights & right != 0
+            })
+            .fold(BitBoard::empty(), |acc, &(_, _, _, rook)| {
+                acc.join(rook.into())
+            })
+    }
+
+    /// A value in `0..16` unique to this combination of rights.
+    pub fn index(self) -> usize {
+        self.rights as usize
+    }
+
+    pub fn to_fen(self) -> String {
+        if self.is_empty() {
+            return "-".to_owned();
+        }
+
+        CASTLING_SQUARES
+            .iter()
+            .filter(|&&(right, _, _, _)| self.rights & right != 0)
+            .map(|&(_, flag, _, _)| flag)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess::Board;
+
+    #[test]
+    fn test_revoke_touched() {
+        let mut rights = CastlingRights::all();
+
+        rights.revoke_touched(RankFile::H1.into());
+        assert!(!rights.has(Player::White, false));
+        assert!(rights.has(Player::White, true));
+        assert_eq!(rights.to_fen(), "Qkq");
+
+        rights.revoke_touched(RankFile::E8.into());
+        assert_eq!(rights.to_fen(), "Q");
+
+        rights.revoke_touched(BitBoard::from(RankFile::E4).join(RankFile::B1.into()));
+        assert_eq!(rights.to_fen(), "Q");
+
+        rights.revoke_touched(RankFile::A1.into());
+        assert!(rights.is_empty());
+        assert_eq!(rights.to_fen(), "-");
+    }
+
+    #[test]
+    fn test_from_placement() {
+        let board = Board::from(
+            "
+            rxxxkxrx
+            xxxxxxxx
+            xxxxxxxx
+            xxxxxxxx
+            xxxxxxxx
+            xxxxxxxx
+            xxxxxxxx
+            RxxxKxxR
+            ",
+            Player::White,
+        )
+        .unwrap();
+
+        assert_eq!(
+            CastlingRights::from_placement(&board.pieces, &board.players).to_fen(),
+            "KQq"
+        );
+
+        let board = Board::from(crate::chess::DEFAULT_BOARD, Player::White).unwrap();
+        assert!(CastlingRights::from_placement(&board.pieces, &board.players).is_empty());
+    }
+
+    #[test]
+    fn test_rook_squares() {
+        let rights = CastlingRights::from_flag('K').unwrap();
+
+        assert_eq!(rights.rook_squares(Player::White), RankFile::H1.into());
+        assert_eq!(rights.rook_squares(Player::Black), BitBoard::empty());
+        assert_eq!(
+            CastlingRights::all().rook_squares(Player::Black),
+            BitBoard::from(RankFile::A8).join(RankFile::H8.into())
+        );
+    }
+}
@@ -14,7 +14,7 @@ pub enum MoveType {
 //  Portable Game Notation (PGN) with just the piece and from/to
 //  Things such as disambiguating moves (e.g. if two rooks can
 //  move to the same spot), captures, and en-passant require
-//  previous board data as well.
+//  previous board data as well.  `Board::to_san` has that data.
 #[derive(PartialEq, Debug, Clone)]
 pub struct Move {
     pub piece_type: PieceType,
@@ -22,7 +22,10 @@ pub struct Move {
     pub to: RankFile,
     pub move_type: MoveType,
     pub is_capture: bool,
+    /// Whether the move puts the other king in check
     pub threatens_king: bool,
+    /// Whether the check leaves the other player without a legal move
+    pub is_checkmate: bool,
 }
 
 impl Default for Move {
@@ -34,10 +37,27 @@ impl Default for Move {
             move_type: MoveType::Standard,
             is_capture: false,
             threatens_king: false,
+            is_checkmate: false,
         }
     }
 }
 
+impl Move {
+    /// Long algebraic notation as UCI uses it, e.g. `e2e4` or `e7e8q`.
+    ///  Castling is written as the king's move, e.g. `e1g1`.
+    pub fn to_uci(&self) -> String {
+        let mut uci = format!("{}{}", self.from, self.to);
+
+        if let MoveType::Promotion { promoted_to } = self.move_type {
+            uci.push(promoted_to.to_char().to_ascii_lowercase());
+        }
+
+        uci
+    }
+}
+
+// A debugging format that needs no board, e.g. `PE2E4`.  See
+//  `Board::to_san` for notation other tools can read.
 impl fmt::Display for Move {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         if let MoveType::Castling { is_queenside } = self.move_type {
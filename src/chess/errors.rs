@@ -20,6 +20,55 @@ impl Display for InvalidStringReason {
     }
 }
 
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum InvalidFenReason {
+    IncorrectFieldCount,
+    IncorrectRankCount,
+    IncorrectRankLength { rank: u8 },
+    InvalidPiece { chr: char },
+    InvalidActivePlayer,
+    InvalidCastlingRights,
+    InvalidEnPassant,
+    InvalidHalfmoveClock,
+    InvalidFullmoveNumber,
+}
+
+impl Display for InvalidFenReason {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            InvalidFenReason::IncorrectFieldCount => {
+                write!(formatter, "Expected between 4 and 6 space separated fields")
+            }
+            InvalidFenReason::IncorrectRankCount => {
+                write!(formatter, "Expected exactly 8 '/' separated ranks")
+            }
+            InvalidFenReason::IncorrectRankLength { rank } => {
+                write!(formatter, "Rank {} does not describe exactly 8 files", rank)
+            }
+            InvalidFenReason::InvalidPiece { chr } => {
+                write!(formatter, "Unrecognized piece placement character: {}", chr)
+            }
+            InvalidFenReason::InvalidActivePlayer => {
+                write!(formatter, "Active player must be either 'w' or 'b'")
+            }
+            InvalidFenReason::InvalidCastlingRights => write!(
+                formatter,
+                "Castling rights must be '-' or a unique combination of 'KQkq'"
+            ),
+            InvalidFenReason::InvalidEnPassant => write!(
+                formatter,
+                "En passant target must be '-' or a square behind an enemy pawn"
+            ),
+            InvalidFenReason::InvalidHalfmoveClock => {
+                write!(formatter, "Halfmove clock must be a non-negative integer")
+            }
+            InvalidFenReason::InvalidFullmoveNumber => {
+                write!(formatter, "Fullmove number must be a positive integer")
+            }
+        }
+    }
+}
+
 #[derive(Debug, Fail, PartialEq)]
 pub enum BoardError {
     #[fail(display = "invalid player id: {}", player_id)]
@@ -34,6 +83,24 @@ pub enum BoardError {
     #[fail(display = "Rank/File exceeded board limits: {} {}", rank, file)]
     OutOfBounds { rank: u8, file: u8 },
 
+    #[fail(display = "Not a square in coordinate notation: {}", text)]
+    InvalidSquare { text: String },
+
     #[fail(display = "Malformed string for board: {}", _0)]
     InvalidString(InvalidStringReason),
+
+    #[fail(display = "Malformed FEN: {}", _0)]
+    InvalidFen(InvalidFenReason),
+}
+
+#[derive(Debug, Fail, PartialEq)]
+pub enum MoveParseError {
+    #[fail(display = "Not a move in algebraic notation: {}", text)]
+    Malformed { text: String },
+
+    #[fail(display = "No legal move matches: {}", text)]
+    Illegal { text: String },
+
+    #[fail(display = "More than one legal move matches: {}", text)]
+    Ambiguous { text: String },
 }
@@ -0,0 +1,60 @@
+use crate::chess::{Move, MoveType};
+
+/// Which of the legal moves to generate.  `Captures` and `Quiets` split
+///  `All` between them, with promotions counted as captures since both
+///  change the material on the board.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum GenType {
+    /// Captures, including en passant, and every promotion
+    Captures,
+    /// Everything else, including castling
+    Quiets,
+    /// Every legal move while in check, and nothing otherwise
+    Evasions,
+    All,
+}
+
+impl GenType {
+    /// Whether `mv` belongs to this kind of move, ignoring whether the
+    ///  player is in check.
+    pub fn includes(self, mv: &Move) -> bool {
+        let is_promotion = matches!(mv.move_type, MoveType::Promotion { .. });
+
+        match self {
+            GenType::Captures => mv.is_capture || is_promotion,
+            GenType::Quiets => !mv.is_capture && !is_promotion,
+            GenType::Evasions | GenType::All => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess::PieceType;
+
+    #[test]
+    fn test_includes() {
+        let quiet = Move::default();
+        let capture = Move {
+            is_capture: true,
+            ..Default::default()
+        };
+        let promotion = Move {
+            move_type: MoveType::Promotion {
+                promoted_to: PieceType::Knight,
+            },
+            ..Default::default()
+        };
+
+        assert!(!GenType::Captures.includes(&quiet));
+        assert!(GenType::Captures.includes(&capture));
+        assert!(GenType::Captures.includes(&promotion));
+
+        assert!(GenType::Quiets.includes(&quiet));
+        assert!(!GenType::Quiets.includes(&capture));
+        assert!(!GenType::Quiets.includes(&promotion));
+
+        assert!(GenType::All.includes(&promotion));
+    }
+}
@@ -0,0 +1,125 @@
+use crate::chess::PLAYER_COUNT;
+use crate::chess::{BitBoard, BitPosition, Player};
+
+lazy_static! {
+    static ref KNIGHT_ATTACKS: [BitBoard; 64] = build_table(knight_shifts);
+    static ref KING_ATTACKS: [BitBoard; 64] = build_table(king_shifts);
+    static ref PAWN_ATTACKS: [[BitBoard; 64]; PLAYER_COUNT] = [
+        build_table(|mask| pawn_attack_shifts(Player::Black, mask)),
+        build_table(|mask| pawn_attack_shifts(Player::White, mask)),
+    ];
+}
+
+fn build_table<F: Fn(BitBoard) -> BitBoard>(shifts: F) -> [BitBoard; 64] {
+    let mut table = [BitBoard::empty(); 64];
+
+    for (index, attacks) in table.iter_mut().enumerate() {
+        *attacks = shifts(BitPosition::from(index as u32).into());
+    }
+
+    table
+}
+
+pub fn knight_attacks(position: BitPosition) -> BitBoard {
+    KNIGHT_ATTACKS[position.right_index as usize]
+}
+
+pub fn king_attacks(position: BitPosition) -> BitBoard {
+    KING_ATTACKS[position.right_index as usize]
+}
+
+/// The two spaces a pawn of `player`'s on `position` could capture on.
+pub fn pawn_attacks(player: Player, position: BitPosition) -> BitBoard {
+    PAWN_ATTACKS[player as usize][position.right_index as usize]
+}
+
+/// Computes every knight move for all the knights in `mask` at once.  The
+///  tables are built from this, and it's kept public to bench against them.
+pub fn knight_shifts(mask: BitBoard) -> BitBoard {
+    // NOTE: My dad could beat up your dad if he wanted
+    let single_horiz_shift = mask.shift_left(1).join(mask.shift_right(1));
+    let double_horiz_shift = mask.shift_left(2).join(mask.shift_right(2));
+
+    // two over, one up
+    let single_vert_shift = double_horiz_shift
+        .shift_up(1)
+        .join(double_horiz_shift.shift_down(1));
+
+    // one over, two up
+    let double_vert_shift = single_horiz_shift
+        .shift_up(2)
+        .join(single_horiz_shift.shift_down(2));
+
+    single_vert_shift.join(double_vert_shift)
+}
+
+/// See `knight_shifts`.
+pub fn king_shifts(mask: BitBoard) -> BitBoard {
+    let row = mask.join(mask.shift_left(1)).join(mask.shift_right(1));
+
+    row.join(row.shift_up(1)).join(row.shift_down(1)) - mask
+}
+
+/// See `knight_shifts`.
+pub fn pawn_attack_shifts(player: Player, mask: BitBoard) -> BitBoard {
+    let direction = match player {
+        Player::White => 1,
+        Player::Black => -1,
+    };
+
+    mask.shift(direction, 1).join(mask.shift(direction, -1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess::RankFile;
+
+    fn squares(rank_files: &[RankFile]) -> BitBoard {
+        rank_files
+            .iter()
+            .fold(BitBoard::empty(), |acc, &rf| acc.join(rf.into()))
+    }
+
+    #[test]
+    fn test_tables_match_shifts() {
+        for index in 0..64 {
+            let position = BitPosition::from(index);
+            let mask = BitBoard::from(position);
+
+            assert_eq!(knight_attacks(position), knight_shifts(mask));
+            assert_eq!(king_attacks(position), king_shifts(mask));
+
+            for &player in [Player::White, Player::Black].iter() {
+                assert_eq!(
+                    pawn_attacks(player, position),
+                    pawn_attack_shifts(player, mask)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_edges() {
+        assert_eq!(
+            knight_attacks(RankFile::A1.into()),
+            squares(&[RankFile::B3, RankFile::C2])
+        );
+        assert_eq!(
+            knight_attacks(RankFile::H5.into()),
+            squares(&[RankFile::G7, RankFile::F6, RankFile::F4, RankFile::G3])
+        );
+        assert_eq!(
+            king_attacks(RankFile::H8.into()),
+            squares(&[RankFile::G8, RankFile::G7, RankFile::H7])
+        );
+        assert_eq!(
+            pawn_attacks(Player::White, RankFile::A2.into()),
+            squares(&[RankFile::B3])
+        );
+        assert_eq!(
+            pawn_attacks(Player::Black, RankFile::E7.into()),
+            squares(&[RankFile::D6, RankFile::F6])
+        );
+    }
+}
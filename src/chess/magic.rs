@@ -0,0 +1,357 @@
+use crate::chess::random::next_random;
+use crate::chess::{BitBoard, BitPosition};
+
+// (rank, file) steps
+const ROOK_DIRECTIONS: [(i32, i32); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+const BISHOP_DIRECTIONS: [(i32, i32); 4] = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
+
+const ROOK_SEED: u64 = 0x6a09_e667_f3bc_c908;
+const BISHOP_SEED: u64 = 0xbb67_ae85_84ca_a73b;
+
+#[derive(Clone, Copy, Default)]
+struct Magic {
+    mask: u64,
+    magic: u64,
+    shift: u32,
+    offset: usize,
+}
+
+impl Magic {
+    fn index(self, occupancy: u64) -> usize {
+        self.offset + ((occupancy & self.mask).wrapping_mul(self.magic) >> self.shift) as usize
+    }
+}
+
+/// The slides for one kind of piece from every square, indexed by hashing
+///  the pieces that could block it with a per-square magic number.
+struct SlidingAttacks {
+    magics: [Magic; 64],
+    attacks: Vec<u64>,
+}
+
+lazy_static! {
+    static ref ROOK_ATTACKS: SlidingAttacks = SlidingAttacks::new(&ROOK_DIRECTIONS, ROOK_SEED);
+    static ref BISHOP_ATTACKS: SlidingAttacks =
+        SlidingAttacks::new(&BISHOP_DIRECTIONS, BISHOP_SEED);
+}
+
+/// Every space a rook on `position` reaches, up to and including the first
+///  piece in `occupancy` along each line.
+pub fn rook_attacks(position: BitPosition, occupancy: BitBoard) -> BitBoard {
+    ROOK_ATTACKS.lookup(position, occupancy)
+}
+
+/// See `rook_attacks`, but along the diagonals.
+pub fn bishop_attacks(position: BitPosition, occupancy: BitBoard) -> BitBoard {
+    BISHOP_ATTACKS.lookup(position, occupancy)
+}
+
+/// The spaces strictly between `from` and `to`, or nothing if no rook or
+///  bishop could slide from one to the other.
+pub fn between(from: BitPosition, to: BitPosition) -> BitBoard {
+    let from_mask = BitBoard::from(from);
+    let to_mask = BitBoard::from(to);
+
+    if !rook_attacks(from, BitBoard::empty())
+        .intersect(to_mask)
+        .is_empty()
+    {
+        rook_attacks(from, to_mask).intersect(rook_attacks(to, from_mask))
+    } else if !bishop_attacks(from, BitBoard::empty())
+        .intersect(to_mask)
+        .is_empty()
+    {
+        bishop_attacks(from, to_mask).intersect(bishop_attacks(to, from_mask))
+    } else {
+        BitBoard::empty()
+    }
+}
+
+/// The whole line through `a` and `b`, from one edge of the board to the
+///  other, or nothing if they don't share a rank, file or diagonal.
+pub fn line(a: BitPosition, b: BitPosition) -> BitBoard {
+    let ends = BitBoard::from(a).join(b.into());
+
+    if !rook_attacks(a, BitBoard::empty())
+        .intersect(b.into())
+        .is_empty()
+    {
+        rook_attacks(a, BitBoard::empty())
+            .intersect(rook_attacks(b, BitBoard::empty()))
+            .join(ends)
+    } else if !bishop_attacks(a, BitBoard::empty())
+        .intersect(b.into())
+        .is_empty()
+    {
+        bishop_attacks(a, BitBoard::empty())
+            .intersect(bishop_attacks(b, BitBoard::empty()))
+            .join(ends)
+    } else {
+        BitBoard::empty()
+    }
+}
+
+impl SlidingAttacks {
+    fn new(directions: &[(i32, i32); 4], seed: u64) -> Self {
+        let mut magics = [Magic::default(); 64];
+        let mut attacks = vec![];
+        let mut state = seed;
+
+        for (square, magic) in magics.iter_mut().enumerate() {
+            let mask = relevant_occupancy(square as i32, directions);
+            let subsets = occupancy_subsets(mask);
+            let slides: Vec<u64> = subsets
+                .iter()
+                .map(|&occupancy| slide(square as i32, occupancy, directions))
+                .collect();
+
+            let offset = attacks.len();
+            attacks.resize(offset + subsets.len(), 0);
+
+            *magic = Magic {
+                offset,
+                ..find_magic(mask, &subsets, &slides, &mut attacks[offset..], &mut state)
+            };
+        }
+
+        SlidingAttacks { magics, attacks }
+    }
+
+    fn lookup(&self, position: BitPosition, occupancy: BitBoard) -> BitBoard {
+        let magic = self.magics[position.right_index as usize];
+
+        BitBoard::from(self.attacks[magic.index(occupancy.into())])
+    }
+}
+
+// Tries random sparse numbers until one maps every blocker subset to a slot
+//  where it doesn't clash with a subset that slides differently.  Fills
+//  `table` with the slides for the magic it returns.
+fn find_magic(
+    mask: u64,
+    subsets: &[u64],
+    slides: &[u64],
+    table: &mut [u64],
+    state: &mut u64,
+) -> Magic {
+    let shift = 64 - mask.count_ones();
+
+    // The attempt that last wrote each slot, so the table never needs clearing
+    let mut written_by = vec![0; table.len()];
+    let mut attempt = 0;
+
+    loop {
+        let magic = next_random(state) & next_random(state) & next_random(state);
+
+        // Magics that don't spread the mask into the top bits almost never work
+        if (mask.wrapping_mul(magic) >> 56).count_ones() < 6 {
+            continue;
+        }
+
+        attempt += 1;
+
+        let fits = subsets.iter().zip(slides).all(|(&occupancy, &attacks)| {
+            let index = (occupancy.wrapping_mul(magic) >> shift) as usize;
+
+            if written_by[index] != attempt {
+                written_by[index] = attempt;
+                table[index] = attacks;
+
+                true
+            } else {
+                table[index] == attacks
+            }
+        });
+
+        if fits {
+            return Magic {
+                mask,
+                magic,
+                shift,
+                offset: 0,
+            };
+        }
+    }
+}
+
+fn is_on_board(rank: i32, file: i32) -> bool {
+    (0..8).contains(&rank) && (0..8).contains(&file)
+}
+
+// The squares whose pieces can block a slide from `square`.  The last
+//  square of each line is left out since nothing lies beyond it to block.
+fn relevant_occupancy(square: i32, directions: &[(i32, i32); 4]) -> u64 {
+    let mut mask = 0;
+
+    for &(rank_step, file_step) in directions.iter() {
+        let mut rank = square / 8 + rank_step;
+        let mut file = square % 8 + file_step;
+
+        while is_on_board(rank + rank_step, file + file_step) {
+            mask |= 1 << (rank * 8 + file);
+
+            rank += rank_step;
+            file += file_step;
+        }
+    }
+
+    mask
+}
+
+// Walks each line one square at a time.  Only used to build the tables.
+fn slide(square: i32, occupancy: u64, directions: &[(i32, i32); 4]) -> u64 {
+    let mut attacks = 0;
+
+    for &(rank_step, file_step) in directions.iter() {
+        let mut rank = square / 8 + rank_step;
+        let mut file = square % 8 + file_step;
+
+        while is_on_board(rank, file) {
+            let bit = 1 << (rank * 8 + file);
+            attacks |= bit;
+
+            if occupancy & bit != 0 {
+                break;
+            }
+
+            rank += rank_step;
+            file += file_step;
+        }
+    }
+
+    attacks
+}
+
+// Every subset of `mask`, counting up through its bits
+fn occupancy_subsets(mask: u64) -> Vec<u64> {
+    let mut subsets = Vec::with_capacity(1 << mask.count_ones());
+    let mut subset = 0u64;
+
+    loop {
+        subsets.push(subset);
+        subset = subset.wrapping_sub(mask) & mask;
+
+        if subset == 0 {
+            return subsets;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess::RankFile;
+
+    #[test]
+    fn test_relevant_occupancy() {
+        assert_eq!(
+            relevant_occupancy(0, &ROOK_DIRECTIONS),
+            0x0001_0101_0101_017e
+        );
+        assert_eq!(
+            relevant_occupancy(27, &BISHOP_DIRECTIONS),
+            0x0040_2214_0014_2200
+        );
+    }
+
+    #[test]
+    fn test_lookups_match_slides() {
+        let mut state = 0x3c6e_f372_fe94_f82b;
+
+        for square in 0..64 {
+            let position = BitPosition::from(square as u32);
+
+            for _ in 0..64 {
+                let occupancy = next_random(&mut state) & next_random(&mut state);
+
+                assert_eq!(
+                    rook_attacks(position, occupancy.into()),
+                    slide(square, occupancy, &ROOK_DIRECTIONS).into()
+                );
+                assert_eq!(
+                    bishop_attacks(position, occupancy.into()),
+                    slide(square, occupancy, &BISHOP_DIRECTIONS).into()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_between_and_line() {
+        assert_eq!(
+            between(RankFile::A1.into(), RankFile::D1.into()),
+            BitBoard::from(RankFile::B1).join(RankFile::C1.into())
+        );
+        assert_eq!(
+            between(RankFile::F5.into(), RankFile::C2.into()),
+            BitBoard::from(RankFile::E4).join(RankFile::D3.into())
+        );
+        assert_eq!(
+            between(RankFile::E4.into(), RankFile::E5.into()),
+            BitBoard::empty()
+        );
+        assert_eq!(
+            between(RankFile::A1.into(), RankFile::B3.into()),
+            BitBoard::empty()
+        );
+
+        assert_eq!(
+            line(RankFile::C3.into(), RankFile::E5.into()),
+            BitBoard::from(0x8040_2010_0804_0201)
+        );
+        assert_eq!(
+            line(RankFile::B4.into(), RankFile::B7.into()),
+            BitBoard::from(0x0202_0202_0202_0202)
+        );
+        assert_eq!(
+            line(RankFile::A1.into(), RankFile::B3.into()),
+            BitBoard::empty()
+        );
+    }
+
+    #[test]
+    fn test_stops_at_blockers() {
+        let occupancy = BitBoard::from(RankFile::D6)
+            .join(RankFile::B4.into())
+            .join(RankFile::F2.into());
+
+        assert_eq!(
+            rook_attacks(RankFile::D4.into(), occupancy),
+            [
+                RankFile::D5,
+                RankFile::D6,
+                RankFile::D3,
+                RankFile::D2,
+                RankFile::D1,
+                RankFile::C4,
+                RankFile::B4,
+                RankFile::E4,
+                RankFile::F4,
+                RankFile::G4,
+                RankFile::H4,
+            ]
+            .iter()
+            .fold(BitBoard::empty(), |acc, &rf| acc.join(rf.into()))
+        );
+
+        assert_eq!(
+            bishop_attacks(RankFile::D4.into(), occupancy),
+            [
+                RankFile::C5,
+                RankFile::B6,
+                RankFile::A7,
+                RankFile::E5,
+                RankFile::F6,
+                RankFile::G7,
+                RankFile::H8,
+                RankFile::C3,
+                RankFile::B2,
+                RankFile::A1,
+                RankFile::E3,
+                RankFile::F2,
+            ]
+            .iter()
+            .fold(BitBoard::empty(), |acc, &rf| acc.join(rf.into()))
+        );
+    }
+}
@@ -1,23 +1,35 @@
 mod bitboard;
 mod bitposition;
 mod board;
+mod castling_rights;
 mod chess_move;
 mod errors;
+mod gen_type;
+pub mod leapers;
+mod magic;
 mod move_generator;
+mod move_list;
 mod piece;
+mod piece_values;
 mod piece_type;
 mod player;
+mod random;
 mod rank_file;
 
 // LOW: When we split these out into crates, all
 //  these do not need to be public
 pub use crate::chess::bitboard::BitBoard;
 pub use crate::chess::bitposition::BitPosition;
-pub use crate::chess::board::Board;
+pub use crate::chess::board::{Board, UndoInfo};
+pub use crate::chess::castling_rights::CastlingRights;
 pub use crate::chess::chess_move::{Move, MoveType};
+pub use crate::chess::errors::{BoardError, MoveParseError};
+pub use crate::chess::gen_type::GenType;
 pub use crate::chess::move_generator::MoveGenerator;
+pub use crate::chess::move_list::MoveList;
 pub use crate::chess::piece::Piece;
 pub use crate::chess::piece_type::PieceType;
+pub use crate::chess::piece_values::PieceValues;
 pub use crate::chess::player::Player;
 pub use crate::chess::rank_file::RankFile;
 
@@ -25,6 +37,15 @@ pub use crate::chess::rank_file::RankFile;
 pub const PIECE_COUNT: usize = 6;
 pub const PLAYER_COUNT: usize = 2;
 
+pub const PROMOTION_PIECES: [PieceType; 4] = [
+    PieceType::Queen,
+    PieceType::Knight,
+    PieceType::Rook,
+    PieceType::Bishop,
+];
+
+pub const STARTING_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
 pub const DEFAULT_BOARD: &str = "
     rnbkqbnr
     pppppppp
@@ -1,8 +1,8 @@
 mod sanity_checks;
 
-use crate::chess::bitboard::{CASTLE_CHECK, KINGSIDE_CASTLE, QUEENSIDE_CASTLE};
-use crate::chess::PIECE_COUNT;
-use crate::chess::{BitBoard, BitPosition, Board, PieceType, Player, RankFile};
+use crate::chess::bitboard::ENDS;
+use crate::chess::{BitBoard, BitPosition, Board, Move, MoveType, PieceType, Player, RankFile};
+use crate::chess::{PIECE_COUNT, PROMOTION_PIECES};
 
 pub struct MoveGenerator {
     root_board: Board,
@@ -16,6 +16,7 @@ pub struct MoveGenerator {
     is_first_move: bool,
     available_moves: BitBoard,
     possible_castle: BitBoard,
+    promotion_index: usize,
 
     piece_index: usize,
 }
@@ -39,6 +40,7 @@ impl MoveGenerator {
             is_first_move: true,
             available_moves: BitBoard::empty(),
             possible_castle: BitBoard::empty(),
+            promotion_index: 0,
 
             piece_index: 0,
         };
@@ -58,52 +60,28 @@ impl MoveGenerator {
         current_position: BitPosition,
         current_position_mask: BitBoard,
     ) -> BitBoard {
-        let moves = match piece_type {
-            PieceType::Rook => self
-                .root_board
-                .find_rook_moves(current_position, current_position_mask),
-            PieceType::Bishop => self
-                .root_board
-                .find_bishop_moves(current_position, current_position_mask),
-            PieceType::Queen => self
-                .root_board
-                .find_queen_moves(current_position, current_position_mask),
-            PieceType::Knight => self
-                .root_board
-                .find_knight_moves(current_position, current_position_mask),
-            PieceType::Pawn => self
-                .root_board
-                .find_pawn_moves(current_position, current_position_mask),
-            PieceType::King => self
-                .root_board
-                .find_king_moves(current_position, current_position_mask),
-        };
+        let moves = self
+            .root_board
+            .find_moves(piece_type, current_position, current_position_mask);
 
         moves - self.player_mask
     }
 
-    fn check_for_castling(&mut self, piece_type: PieceType, current_position_mask: BitBoard) {
+    fn check_for_castling(&mut self, piece_type: PieceType, current_position: BitPosition) {
         if piece_type != PieceType::King
-            || self
+            || !self
                 .root_board
-                .unmoved_pieces
-                .intersect(current_position_mask)
-                .is_empty()
+                .is_castling_king(self.player, current_position.into())
         {
             return;
         }
 
-        self.possible_castle = self.root_board.pieces[PieceType::Rook as usize]
-            .intersect(self.player_mask)
-            .intersect(self.root_board.unmoved_pieces);
-
-        if !self.possible_castle.is_empty()
-            && self
-                .root_board
-                .is_attacked(self.player, current_position_mask)
-        {
-            self.possible_castle = BitBoard::empty();
-        }
+        self.possible_castle = self
+            .root_board
+            .castling_rights
+            .rook_squares(self.player)
+            .intersect(self.root_board.pieces[PieceType::Rook as usize])
+            .intersect(self.player_mask);
     }
 
     fn generate_next_castling_board(&mut self) -> Option<Board> {
@@ -112,21 +90,7 @@ impl MoveGenerator {
 
             let is_queenside = rf.file() == 0;
 
-            let mut spaces = if is_queenside {
-                QUEENSIDE_CASTLE
-            } else {
-                KINGSIDE_CASTLE
-            };
-
-            if self.player == Player::Black {
-                spaces = spaces.shift_up(7);
-            }
-
-            if spaces.intersect(self.all_pieces).is_empty()
-                && !self
-                    .root_board
-                    .is_attacked(self.player, spaces.intersect(CASTLE_CHECK))
-            {
+            if self.root_board.can_castle(is_queenside) {
                 return Some(self.root_board.perform_castle(is_queenside));
             }
         }
@@ -158,7 +122,7 @@ impl MoveGenerator {
                 }
             }
 
-            self.check_for_castling(piece_type, current_position_mask);
+            self.check_for_castling(piece_type, current_position);
         }
 
         if piece_type == PieceType::King {
@@ -174,6 +138,10 @@ impl MoveGenerator {
         let next_position = self.available_moves.first_bit_position();
         let next_position_mask = BitBoard::from(next_position);
 
+        if piece_type == PieceType::Pawn && !next_position_mask.intersect(ENDS).is_empty() {
+            return Some(self.generate_next_promotion_board(current_position, next_position));
+        }
+
         let board = self.root_board.move_piece(
             piece_type,
             current_position,
@@ -187,6 +155,36 @@ impl MoveGenerator {
 
         Some(board)
     }
+
+    // Each promotion square yields one board per piece in PROMOTION_PIECES
+    //  before we move on to the next available move.
+    fn generate_next_promotion_board(
+        &mut self,
+        current_position: BitPosition,
+        next_position: BitPosition,
+    ) -> Board {
+        let next_position_mask = BitBoard::from(next_position);
+
+        let board = self.root_board.apply(&Move {
+            piece_type: PieceType::Pawn,
+            from: current_position.into(),
+            to: next_position.into(),
+            move_type: MoveType::Promotion {
+                promoted_to: PROMOTION_PIECES[self.promotion_index],
+            },
+            is_capture: !next_position_mask.intersect(self.enemy_mask).is_empty(),
+            ..Default::default()
+        });
+
+        self.promotion_index += 1;
+
+        if self.promotion_index == PROMOTION_PIECES.len() {
+            self.promotion_index = 0;
+            self.available_moves -= next_position_mask;
+        }
+
+        board
+    }
 }
 
 impl Iterator for MoveGenerator {
@@ -221,7 +219,7 @@ impl Iterator for MoveGenerator {
             let piece_type: PieceType = num::FromPrimitive::from_usize(self.piece_index).unwrap();
 
             match self.generate_next_move(piece_type, rightmost_position, piece_mask) {
-                Some(board) => {
+                Some(mut board) => {
                     // LOW: This loop only matters for tests where we have > 1 king.
                     //  Possibly remove and optimize for release mode?
                     let king_mask = board.players[self.player as usize]
@@ -235,6 +233,10 @@ impl Iterator for MoveGenerator {
                         }
                     }
 
+                    if let Some(mv) = board.prev_move.as_mut() {
+                        self.root_board.mark_check(mv);
+                    }
+
                     return Some(board);
                 }
                 None => {
@@ -245,3 +247,62 @@ impl Iterator for MoveGenerator {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess::MoveType;
+
+    fn castles(fen: &str) -> Vec<MoveType> {
+        Board::from_fen(fen)
+            .unwrap()
+            .generate_moves()
+            .filter_map(|board| board.prev_move)
+            .map(|m| m.move_type)
+            .filter(|move_type| matches!(move_type, MoveType::Castling { .. }))
+            .collect()
+    }
+
+    #[test]
+    fn test_castling_follows_rights() {
+        assert_eq!(
+            castles("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1"),
+            vec![
+                MoveType::Castling { is_queenside: true },
+                MoveType::Castling {
+                    is_queenside: false
+                }
+            ]
+        );
+
+        assert_eq!(
+            castles("r3k2r/8/8/8/8/8/8/R3K2R w Kq - 0 1"),
+            vec![MoveType::Castling {
+                is_queenside: false
+            }]
+        );
+
+        assert_eq!(
+            castles("r3k2r/8/8/8/8/8/8/R3K2R b Kq - 0 1"),
+            vec![MoveType::Castling { is_queenside: true }]
+        );
+
+        assert_eq!(castles("r3k2r/8/8/8/8/8/8/R3K2R w - - 0 1"), vec![]);
+    }
+
+    #[test]
+    fn test_castling_squares() {
+        // The knight only blocks the queenside, the bishop only attacks the kingside
+        assert_eq!(castles("4k3/8/8/8/8/7b/8/RN2K2R w KQ - 0 1"), vec![]);
+        assert_eq!(
+            castles("4k3/8/8/8/8/8/8/RN2K2R w KQ - 0 1"),
+            vec![MoveType::Castling {
+                is_queenside: false
+            }]
+        );
+        assert_eq!(
+            castles("4k3/8/8/8/8/7b/8/R3K2R w KQ - 0 1"),
+            vec![MoveType::Castling { is_queenside: true }]
+        );
+    }
+}
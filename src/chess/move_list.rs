@@ -0,0 +1,61 @@
+use std::ops::{Deref, DerefMut};
+use std::slice::Iter;
+use std::vec::IntoIter;
+
+use crate::chess::Move;
+
+/// The moves available in a position.  Derefs to a slice, so everything
+///  from `len` to `iter().find(...)` works as it would on a `Vec`.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct MoveList {
+    moves: Vec<Move>,
+}
+
+impl MoveList {
+    pub fn new() -> Self {
+        // Most positions have fewer than 64 moves available
+        MoveList {
+            moves: Vec::with_capacity(64),
+        }
+    }
+
+    pub fn push(&mut self, mv: Move) {
+        self.moves.push(mv);
+    }
+
+    pub fn retain<F: FnMut(&Move) -> bool>(&mut self, f: F) {
+        self.moves.retain(f);
+    }
+}
+
+impl Deref for MoveList {
+    type Target = [Move];
+
+    fn deref(&self) -> &Self::Target {
+        &self.moves
+    }
+}
+
+impl DerefMut for MoveList {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.moves
+    }
+}
+
+impl IntoIterator for MoveList {
+    type Item = Move;
+    type IntoIter = IntoIter<Move>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.moves.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a MoveList {
+    type Item = &'a Move;
+    type IntoIter = Iter<'a, Move>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.moves.iter()
+    }
+}
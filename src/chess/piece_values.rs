@@ -0,0 +1,39 @@
+use crate::chess::PieceType;
+
+/// What each piece is worth in centipawns, for weighing up exchanges.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct PieceValues {
+    pub pawn: i32,
+    pub rook: i32,
+    pub knight: i32,
+    pub bishop: i32,
+    pub queen: i32,
+    /// Kept far above everything else so an exchange never gives it up
+    pub king: i32,
+}
+
+impl Default for PieceValues {
+    fn default() -> Self {
+        PieceValues {
+            pawn: 100,
+            rook: 500,
+            knight: 300,
+            bishop: 300,
+            queen: 900,
+            king: 20_000,
+        }
+    }
+}
+
+impl PieceValues {
+    pub fn value(&self, piece_type: PieceType) -> i32 {
+        match piece_type {
+            PieceType::Pawn => self.pawn,
+            PieceType::Rook => self.rook,
+            PieceType::Knight => self.knight,
+            PieceType::Bishop => self.bishop,
+            PieceType::Queen => self.queen,
+            PieceType::King => self.king,
+        }
+    }
+}
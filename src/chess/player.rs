@@ -14,6 +14,15 @@ impl From<char> for Player {
     }
 }
 
+impl Player {
+    pub fn opponent(self) -> Player {
+        match self {
+            Player::White => Player::Black,
+            Player::Black => Player::White,
+        }
+    }
+}
+
 #[test]
 fn test_player_from_str() {
     assert_eq!(Player::from('Q'), Player::White);
@@ -32,3 +41,9 @@ fn test_player_from_str() {
     assert_eq!(Player::from('r'), Player::Black);
     assert_eq!(Player::from('x'), Player::Black);
 }
+
+#[test]
+fn test_opponent() {
+    assert_eq!(Player::White.opponent(), Player::Black);
+    assert_eq!(Player::Black.opponent(), Player::White);
+}
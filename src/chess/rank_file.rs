@@ -1,5 +1,9 @@
+use std::fmt;
+use std::str::FromStr;
+
 use num;
 
+use crate::chess::errors::BoardError;
 use crate::chess::BitPosition;
 
 // TODO: I don't know if this needs to be separate from BitPosition
@@ -31,6 +35,61 @@ impl RankFile {
     pub fn file(self) -> u8 {
         self as u8 % 8
     }
+
+    /// The file's letter in coordinate notation, `a` to `h`.
+    pub fn file_char(self) -> char {
+        (b'a' + self.file()) as char
+    }
+
+    /// The rank's digit in coordinate notation, `1` to `8`.
+    pub fn rank_char(self) -> char {
+        (b'1' + self.rank()) as char
+    }
+
+    /// The file a letter from `file_char` stands for.
+    pub fn parse_file(chr: char) -> Option<u8> {
+        if ('a'..='h').contains(&chr) {
+            Some(chr as u8 - b'a')
+        } else {
+            None
+        }
+    }
+
+    /// The rank a digit from `rank_char` stands for.
+    pub fn parse_rank(chr: char) -> Option<u8> {
+        if ('1'..='8').contains(&chr) {
+            Some(chr as u8 - b'1')
+        } else {
+            None
+        }
+    }
+}
+
+/// Coordinate notation, e.g. `e4`.
+impl fmt::Display for RankFile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.file_char(), self.rank_char())
+    }
+}
+
+impl FromStr for RankFile {
+    type Err = BoardError;
+
+    fn from_str(square: &str) -> Result<Self, Self::Err> {
+        let invalid = || BoardError::InvalidSquare {
+            text: square.to_owned(),
+        };
+
+        let mut chars = square.chars();
+
+        let file = chars.next().and_then(RankFile::parse_file);
+        let rank = chars.next().and_then(RankFile::parse_rank);
+
+        match (file, rank, chars.next()) {
+            (Some(file), Some(rank), None) => Ok(BitPosition::from((rank, file)).into()),
+            _ => Err(invalid()),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -59,4 +118,28 @@ mod tests {
         assert_eq!(RankFile::D1.rank(), 0);
         assert_eq!(RankFile::H8.rank(), 7);
     }
+
+    #[test]
+    fn test_coordinates() {
+        assert_eq!(RankFile::E4.to_string(), "e4");
+        assert_eq!(RankFile::A1.to_string(), "a1");
+        assert_eq!(RankFile::H8.to_string(), "h8");
+
+        assert_eq!("e4".parse(), Ok(RankFile::E4));
+        assert_eq!("h1".parse(), Ok(RankFile::H1));
+
+        for index in 0..64 {
+            let rank_file = RankFile::from(BitPosition::from(index));
+            assert_eq!(rank_file.to_string().parse(), Ok(rank_file));
+        }
+
+        for &square in ["", "e", "E4", "e9", "i1", "e44", "4e"].iter() {
+            assert_eq!(
+                square.parse::<RankFile>(),
+                Err(BoardError::InvalidSquare {
+                    text: square.to_owned()
+                })
+            );
+        }
+    }
 }
@@ -0,0 +1,28 @@
+use crate::chess::{BoardError, MoveParseError};
+
+#[derive(Debug, Fail, PartialEq)]
+pub enum EpdError {
+    #[fail(display = "Expected 4 position fields before the operations: {}", line)]
+    MissingFields { line: String },
+
+    #[fail(display = "Invalid position: {}", _0)]
+    InvalidPosition(BoardError),
+
+    #[fail(display = "Unterminated string operand: {}", line)]
+    UnterminatedString { line: String },
+
+    #[fail(display = "The {} operation is missing its operand", opcode)]
+    MissingOperand { opcode: String },
+
+    #[fail(
+        display = "The {} operation has an invalid operand: {}",
+        opcode, operand
+    )]
+    InvalidOperand { opcode: String, operand: String },
+
+    #[fail(display = "The {} operation has an invalid move: {}", opcode, error)]
+    InvalidMove {
+        opcode: String,
+        error: MoveParseError,
+    },
+}
@@ -0,0 +1,335 @@
+mod errors;
+
+use crate::chess::{Board, Move};
+
+pub use crate::epd::errors::EpdError;
+
+/// One line of an EPD file: a position followed by `;` terminated
+///  operations, e.g. `... w - - bm Qg6; id "WAC.001";`
+#[derive(Debug, Clone)]
+pub struct EpdPosition {
+    /// The position, with its clocks taken from `hmvc` and `fmvn` if they
+    ///  are given
+    pub board: Board,
+    /// Every operation but `hmvc` and `fmvn`, in the order they appeared
+    pub operations: Vec<EpdOperation>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EpdOperation {
+    /// `bm`: playing any of these solves the position
+    BestMoves(Vec<Move>),
+    /// `am`: playing any of these fails it
+    AvoidMoves(Vec<Move>),
+    /// `id`: the position's name within its suite, e.g. `WAC.001`
+    Id(String),
+    /// `c0` to `c9`
+    Comment { index: u8, text: String },
+    Other {
+        opcode: String,
+        operands: Vec<String>,
+    },
+}
+
+impl EpdPosition {
+    pub fn from_epd(line: &str) -> Result<EpdPosition, EpdError> {
+        let mut rest = line.trim();
+        let mut fields = vec![];
+
+        for _ in 0..4 {
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+
+            fields.push(&rest[..end]);
+            rest = rest[end..].trim_start();
+        }
+
+        if fields.iter().any(|field| field.is_empty()) {
+            return Err(EpdError::MissingFields {
+                line: line.to_owned(),
+            });
+        }
+
+        let raw_operations =
+            split_operations(rest).ok_or_else(|| EpdError::UnterminatedString {
+                line: line.to_owned(),
+            })?;
+
+        // The clocks go through the FEN parser so they're checked the same way
+        let mut clocks = ["0".to_owned(), "1".to_owned()];
+        let mut remaining = vec![];
+
+        for (opcode, operands) in raw_operations {
+            let clock = match opcode.as_str() {
+                "hmvc" => 0,
+                "fmvn" => 1,
+                _ => {
+                    remaining.push((opcode, operands));
+                    continue;
+                }
+            };
+
+            clocks[clock] = single_operand(&opcode, operands)?;
+        }
+
+        let fen = format!("{} {}", fields.join(" "), clocks.join(" "));
+        let board = Board::from_fen(&fen).map_err(EpdError::InvalidPosition)?;
+
+        let operations = remaining
+            .into_iter()
+            .map(|(opcode, operands)| parse_operation(&board, opcode, operands))
+            .collect::<Result<_, _>>()?;
+
+        Ok(EpdPosition { board, operations })
+    }
+
+    pub fn id(&self) -> Option<&str> {
+        self.operations
+            .iter()
+            .find_map(|operation| match operation {
+                EpdOperation::Id(id) => Some(id.as_str()),
+                _ => None,
+            })
+    }
+
+    /// Whether there's a `bm` or `am` to score a move against.
+    pub fn has_criteria(&self) -> bool {
+        self.operations.iter().any(|operation| {
+            matches!(
+                operation,
+                EpdOperation::BestMoves(_) | EpdOperation::AvoidMoves(_)
+            )
+        })
+    }
+
+    /// Whether `mv` is one of the best moves and none of the ones to avoid.
+    pub fn is_solved_by(&self, mv: &Move) -> bool {
+        self.operations.iter().all(|operation| match operation {
+            EpdOperation::BestMoves(moves) => moves.iter().any(|best| same_move(best, mv)),
+            EpdOperation::AvoidMoves(moves) => !moves.iter().any(|avoid| same_move(avoid, mv)),
+            _ => true,
+        })
+    }
+}
+
+// Ignores the check flags, which depend on how the move was generated
+fn same_move(a: &Move, b: &Move) -> bool {
+    a.from == b.from && a.to == b.to && a.move_type == b.move_type
+}
+
+fn parse_operation(
+    board: &Board,
+    opcode: String,
+    operands: Vec<String>,
+) -> Result<EpdOperation, EpdError> {
+    let operation = match opcode.as_str() {
+        "bm" => EpdOperation::BestMoves(parse_moves(board, &opcode, &operands)?),
+        "am" => EpdOperation::AvoidMoves(parse_moves(board, &opcode, &operands)?),
+        "id" => EpdOperation::Id(single_operand(&opcode, operands)?),
+        _ => match comment_index(&opcode) {
+            Some(index) => EpdOperation::Comment {
+                index,
+                text: single_operand(&opcode, operands)?,
+            },
+            None => EpdOperation::Other { opcode, operands },
+        },
+    };
+
+    Ok(operation)
+}
+
+fn parse_moves(board: &Board, opcode: &str, operands: &[String]) -> Result<Vec<Move>, EpdError> {
+    if operands.is_empty() {
+        return Err(EpdError::MissingOperand {
+            opcode: opcode.to_owned(),
+        });
+    }
+
+    operands
+        .iter()
+        .map(|san| {
+            board.parse_san(san).map_err(|error| EpdError::InvalidMove {
+                opcode: opcode.to_owned(),
+                error,
+            })
+        })
+        .collect()
+}
+
+fn single_operand(opcode: &str, mut operands: Vec<String>) -> Result<String, EpdError> {
+    match operands.len() {
+        0 => Err(EpdError::MissingOperand {
+            opcode: opcode.to_owned(),
+        }),
+        1 => Ok(operands.remove(0)),
+        _ => Err(EpdError::InvalidOperand {
+            opcode: opcode.to_owned(),
+            operand: operands.join(" "),
+        }),
+    }
+}
+
+fn comment_index(opcode: &str) -> Option<u8> {
+    let mut chars = opcode.chars();
+
+    match (chars.next(), chars.next(), chars.next()) {
+        (Some('c'), Some(digit), None) => digit.to_digit(10).map(|index| index as u8),
+        _ => None,
+    }
+}
+
+// Splits the operations into their opcode and operands.  Quoted operands
+//  keep their spaces and semicolons, and the last `;` may be left off.
+//  `None` if a quote is never closed.
+fn split_operations(text: &str) -> Option<Vec<(String, Vec<String>)>> {
+    let mut operations = vec![];
+    let mut words: Vec<String> = vec![];
+    let mut chars = text.chars().peekable();
+
+    while let Some(&chr) = chars.peek() {
+        match chr {
+            ';' => {
+                chars.next();
+
+                if !words.is_empty() {
+                    let opcode = words.remove(0);
+                    operations.push((opcode, words.split_off(0)));
+                }
+            }
+            '"' => {
+                chars.next();
+
+                let mut word = String::new();
+
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        chr => word.push(chr),
+                    }
+                }
+
+                words.push(word);
+            }
+            chr if chr.is_whitespace() => {
+                chars.next();
+            }
+            _ => {
+                let mut word = String::new();
+
+                while let Some(&chr) = chars.peek() {
+                    if chr == ';' || chr == '"' || chr.is_whitespace() {
+                        break;
+                    }
+
+                    word.push(chr);
+                    chars.next();
+                }
+
+                words.push(word);
+            }
+        }
+    }
+
+    if !words.is_empty() {
+        let opcode = words.remove(0);
+        operations.push((opcode, words));
+    }
+
+    Some(operations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess::MoveParseError;
+
+    const WAC_001: &str =
+        "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - bm Qg6; id \"WAC.001\";";
+
+    #[test]
+    fn test_best_move() {
+        let position = EpdPosition::from_epd(WAC_001).unwrap();
+        let board = &position.board;
+
+        assert_eq!(
+            board.to_fen(),
+            "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - 0 1"
+        );
+        assert_eq!(position.id(), Some("WAC.001"));
+        assert!(position.has_criteria());
+
+        assert!(position.is_solved_by(&board.parse_san("Qg6").unwrap()));
+        assert!(!position.is_solved_by(&board.parse_san("Qg4").unwrap()));
+    }
+
+    #[test]
+    fn test_operations() {
+        let position = EpdPosition::from_epd(
+            "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - \
+             am Ba6 Nxe5; c0 \"Quoted; with spaces\"; hmvc 2; fmvn 3; acd 12",
+        )
+        .unwrap();
+        let board = &position.board;
+
+        assert_eq!(board.halfmove_clock, 2);
+        assert_eq!(board.fullmove_number, 3);
+        assert_eq!(position.id(), None);
+
+        assert_eq!(
+            position.operations,
+            vec![
+                EpdOperation::AvoidMoves(vec![
+                    board.parse_san("Ba6").unwrap(),
+                    board.parse_san("Nxe5").unwrap(),
+                ]),
+                EpdOperation::Comment {
+                    index: 0,
+                    text: "Quoted; with spaces".to_owned(),
+                },
+                EpdOperation::Other {
+                    opcode: "acd".to_owned(),
+                    operands: vec!["12".to_owned()],
+                },
+            ]
+        );
+
+        assert!(position.is_solved_by(&board.parse_san("Bc4").unwrap()));
+        assert!(!position.is_solved_by(&board.parse_san("Nxe5").unwrap()));
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |line: &str| EpdPosition::from_epd(line).unwrap_err();
+
+        assert_eq!(
+            error("8/8/8/8 w -"),
+            EpdError::MissingFields {
+                line: "8/8/8/8 w -".to_owned()
+            }
+        );
+        assert!(matches!(
+            error("8/8/8/8 w - - bm Kg1;"),
+            EpdError::InvalidPosition(_)
+        ));
+        assert_eq!(
+            error("4k3/8/8/8/8/8/8/4K3 w - - id \"open;"),
+            EpdError::UnterminatedString {
+                line: "4k3/8/8/8/8/8/8/4K3 w - - id \"open;".to_owned()
+            }
+        );
+        assert_eq!(
+            error("4k3/8/8/8/8/8/8/4K3 w - - bm;"),
+            EpdError::MissingOperand {
+                opcode: "bm".to_owned()
+            }
+        );
+        assert_eq!(
+            error("4k3/8/8/8/8/8/8/4K3 w - - bm Ke3;"),
+            EpdError::InvalidMove {
+                opcode: "bm".to_owned(),
+                error: MoveParseError::Illegal {
+                    text: "Ke3".to_owned()
+                },
+            }
+        );
+    }
+}
@@ -18,6 +18,7 @@ pub const WHITE_EN_PASSANT: Move = Move {
     move_type: MoveType::Standard,
     is_capture: false,
     threatens_king: false,
+    is_checkmate: false,
 };
 
 pub const BLACK_PAWN_TEST: &str = "
@@ -38,8 +39,31 @@ pub const BLACK_EN_PASSANT: Move = Move {
     move_type: MoveType::Standard,
     is_capture: false,
     threatens_king: false,
+    is_checkmate: false,
 };
 
+pub const WHITE_PROMOTION_TEST: &str = "
+    nxxxxxbr
+    xPxxxxPx
+    xxxxxxxx
+    xxxxxxxx
+    xxxxxxxx
+    xxxxxxxx
+    xxxxxxxx
+    xxxxxxxx
+    ";
+
+pub const BLACK_PROMOTION_TEST: &str = "
+    xxxxxxxx
+    xxxxxxxx
+    xxxxxxxx
+    xxxxxxxx
+    xxxxxxxx
+    xxxxxxxx
+    xpxxxxpx
+    NxxxxxBR
+    ";
+
 pub const WHITE_ROOK_TEST: &str = "
     Rxxnxxxx
     xxxxxxrx
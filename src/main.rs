@@ -16,13 +16,161 @@ extern crate lazy_static;
 define_uncover_macros!(enable_if(cfg!(debug_assertions)));
 
 pub mod chess;
+pub mod epd;
 pub mod fixtures;
+pub mod pgn;
+pub mod search;
 pub mod test_moves;
 
+use std::env;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::time::Instant;
+
 use crate::chess::DEFAULT_BOARD;
 use crate::chess::{BitBoard, Board, Player};
+use crate::epd::EpdPosition;
+
+// How many plies `chess_ai epd` searches each position
+const EPD_DEPTH: u32 = 4;
+
+const USAGE: &str = "Usage: chess_ai perft <fen> <depth>\n       chess_ai epd <file>...";
 
 fn main() -> Result<(), failure::Error> {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.first().map(String::as_str) {
+        Some("perft") => run_perft(&args[1..]),
+        Some("epd") => run_epd(&args[1..]),
+        Some(_) => bail!(USAGE),
+        None => print_examples(),
+    }
+}
+
+// Prints the node count under each legal move in UCI notation, then the
+//  total, in the same shape as other engines' divide output so the two can
+//  be diffed.
+fn run_perft(args: &[String]) -> Result<(), failure::Error> {
+    if args.len() != 2 {
+        bail!(USAGE);
+    }
+
+    let board = Board::from_fen(&args[0])?;
+    let depth: u32 = args[1]
+        .parse()
+        .map_err(|_| format_err!("Invalid depth: {}", args[1]))?;
+
+    let start = Instant::now();
+    let divided = board.divide(depth);
+    let elapsed = start.elapsed();
+
+    for (mv, nodes) in divided.iter() {
+        println!("{}: {}", mv.to_uci(), nodes);
+    }
+
+    let total = if depth == 0 {
+        1
+    } else {
+        divided.iter().map(|&(_, nodes)| nodes).sum()
+    };
+
+    println!();
+    println!("Nodes searched: {}", total);
+    println!(
+        "Time: {}.{:03}s",
+        elapsed.as_secs(),
+        elapsed.subsec_millis()
+    );
+
+    Ok(())
+}
+
+// Scores each file as a suite of its own.  Lines that can't be parsed are
+//  reported and count as failures, while positions with no `bm` or `am` are
+//  left out of the score.
+fn run_epd(paths: &[String]) -> Result<(), failure::Error> {
+    if paths.is_empty() {
+        bail!(USAGE);
+    }
+
+    // Shared by every position, so the table is only allocated once
+    let mut searcher = search::Searcher::new();
+    let mut summaries = vec![];
+
+    for path in paths.iter() {
+        let input = BufReader::new(File::open(path)?);
+        let mut solved = 0;
+        let mut scored = 0;
+
+        for (index, line) in input.lines().enumerate() {
+            let line = line?;
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let position = match EpdPosition::from_epd(&line) {
+                Ok(position) => position,
+                Err(error) => {
+                    println!("{}:{}: {}", path, index + 1, error);
+                    scored += 1;
+                    continue;
+                }
+            };
+
+            let label = match position.id() {
+                Some(id) => id.to_owned(),
+                None => format!("{}:{}", path, index + 1),
+            };
+
+            if !position.has_criteria() {
+                println!("{}: skipped, no bm or am", label);
+                continue;
+            }
+
+            scored += 1;
+
+            match searcher.search(&position.board, EPD_DEPTH).best_move {
+                Some(mv) => {
+                    let is_solved = position.is_solved_by(&mv);
+
+                    if is_solved {
+                        solved += 1;
+                    }
+
+                    println!(
+                        "{}: {} {}",
+                        label,
+                        if is_solved { "ok" } else { "FAIL" },
+                        position.board.to_san(&mv)
+                    );
+                }
+                None => println!("{}: FAIL no legal moves", label),
+            }
+        }
+
+        summaries.push((path, solved, scored));
+    }
+
+    println!();
+
+    for (path, solved, scored) in summaries {
+        let percentage = if scored == 0 {
+            0.0
+        } else {
+            100.0 * f64::from(solved) / f64::from(scored)
+        };
+
+        println!(
+            "{}: {}/{} solved ({:.1}%)",
+            path, solved, scored, percentage
+        );
+    }
+
+    Ok(())
+}
+
+fn print_examples() -> Result<(), failure::Error> {
     let pieces: [BitBoard; 6] = [
         BitBoard::from(1),
         BitBoard::from(1 << 8),
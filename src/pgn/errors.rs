@@ -0,0 +1,29 @@
+use std::io;
+
+use crate::chess::MoveParseError;
+
+/// Everything but `Io` only spoils the game it was found in, and the reader
+///  carries on with the next one.  Games are numbered from 1.
+#[derive(Debug, Fail)]
+pub enum PgnError {
+    #[fail(display = "Failed to read PGN: {}", _0)]
+    Io(io::Error),
+
+    #[fail(display = "Game {}: malformed tag pair: {}", game, line)]
+    InvalidTag { game: usize, line: String },
+
+    #[fail(display = "Game {}: invalid FEN tag: {}", game, fen)]
+    InvalidFen { game: usize, fen: String },
+
+    #[fail(display = "Game {}: {}", game, error)]
+    InvalidMove { game: usize, error: MoveParseError },
+
+    #[fail(display = "Game {}: unexpected {} in movetext", game, token)]
+    UnexpectedToken { game: usize, token: String },
+
+    #[fail(
+        display = "Game {}: movetext ended inside a comment or variation",
+        game
+    )]
+    Unterminated { game: usize },
+}
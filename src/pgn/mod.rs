@@ -0,0 +1,113 @@
+mod errors;
+mod reader;
+mod writer;
+
+use std::fmt;
+
+use crate::chess::{Board, Move};
+
+pub use crate::pgn::errors::PgnError;
+pub use crate::pgn::reader::PgnReader;
+pub use crate::pgn::writer::PgnWriter;
+
+/// One game from a PGN file, with its moves already checked against the
+///  board they were played on.
+#[derive(Debug, Clone)]
+pub struct Game {
+    /// The tag pairs in the order they appeared, e.g. `("White", "Carlsen")`
+    pub tags: Vec<(String, String)>,
+    /// The position the game started from, taken from the `FEN` tag if it
+    ///  has one
+    pub starting_board: Board,
+    pub mainline: Variation,
+    pub result: GameResult,
+}
+
+impl Game {
+    /// An untagged, unfinished game of `moves` played from `starting_board`,
+    ///  e.g. to export one `chess_ai` has played.
+    pub fn from_moves(starting_board: Board, moves: &[Move]) -> Game {
+        let moves = moves
+            .iter()
+            .map(|mv| PgnMove {
+                mv: mv.clone(),
+                nags: vec![],
+                comments: vec![],
+                variations: vec![],
+            })
+            .collect();
+
+        Game {
+            tags: vec![],
+            starting_board,
+            mainline: Variation {
+                comments: vec![],
+                moves,
+            },
+            result: GameResult::Unfinished,
+        }
+    }
+
+    /// The value of the first tag named `name`.
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(tag_name, _)| tag_name == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// A sequence of moves, either the mainline or an alternative to one of its
+///  moves.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Variation {
+    /// Comments that come before the first move
+    pub comments: Vec<String>,
+    pub moves: Vec<PgnMove>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PgnMove {
+    pub mv: Move,
+    /// Numeric Annotation Glyphs, with `!`, `?` and the like turned into
+    ///  their numbers
+    pub nags: Vec<u8>,
+    /// Comments that come after the move
+    pub comments: Vec<String>,
+    /// Alternatives to this move, each played from the position before it
+    pub variations: Vec<Variation>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GameResult {
+    WhiteWins,
+    BlackWins,
+    Draw,
+    /// Still going, abandoned, or just not known
+    Unfinished,
+}
+
+impl GameResult {
+    pub fn from(token: &str) -> Option<GameResult> {
+        let result = match token {
+            "1-0" => GameResult::WhiteWins,
+            "0-1" => GameResult::BlackWins,
+            "1/2-1/2" => GameResult::Draw,
+            "*" => GameResult::Unfinished,
+            _ => return None,
+        };
+
+        Some(result)
+    }
+}
+
+impl fmt::Display for GameResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameResult::WhiteWins => write!(f, "1-0"),
+            GameResult::BlackWins => write!(f, "0-1"),
+            GameResult::Draw => write!(f, "1/2-1/2"),
+            GameResult::Unfinished => write!(f, "*"),
+        }
+    }
+}
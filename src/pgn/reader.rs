@@ -0,0 +1,511 @@
+use std::io::{self, BufRead};
+
+use crate::chess::{Board, STARTING_FEN};
+use crate::pgn::{Game, GameResult, PgnError, PgnMove, Variation};
+
+/// Reads games one at a time from a PGN file or any other buffered source,
+///  never holding more than one game's text in memory.
+///
+/// A game that can't be parsed comes out as an `Err` and the reader moves on
+///  to the next one.  Only a failure to read ends the iteration early.
+pub struct PgnReader<R: BufRead> {
+    input: R,
+    // The first tag line of the next game, read while looking for the end
+    //  of the previous one
+    pending_line: Option<String>,
+    games_read: usize,
+    is_done: bool,
+}
+
+impl<R: BufRead> PgnReader<R> {
+    pub fn new(input: R) -> Self {
+        PgnReader {
+            input,
+            pending_line: None,
+            games_read: 0,
+            is_done: false,
+        }
+    }
+
+    // The tag lines and movetext of the next game.  A game ends where the
+    //  next one's tags start, or at the end of the input.
+    fn next_game_text(&mut self) -> Option<io::Result<(Vec<String>, String)>> {
+        let mut tag_lines = vec![];
+        let mut movetext = String::new();
+        let mut in_comment = false;
+
+        loop {
+            let line = match self.pending_line.take() {
+                Some(line) => line,
+                None => {
+                    let mut line = String::new();
+
+                    match self.input.read_line(&mut line) {
+                        Ok(0) => break,
+                        Ok(_) => line,
+                        Err(error) => return Some(Err(error)),
+                    }
+                }
+            };
+
+            if !in_comment {
+                // Lines starting with `%` are meant for other software
+                if line.starts_with('%') {
+                    continue;
+                }
+
+                if line.trim_start().starts_with('[') {
+                    if !movetext.trim().is_empty() {
+                        self.pending_line = Some(line);
+                        break;
+                    }
+
+                    tag_lines.push(line.trim().to_owned());
+                    continue;
+                }
+            }
+
+            in_comment = ends_in_comment(&line, in_comment);
+            movetext.push_str(&line);
+        }
+
+        if tag_lines.is_empty() && movetext.trim().is_empty() {
+            None
+        } else {
+            Some(Ok((tag_lines, movetext)))
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for PgnReader<R> {
+    type Item = Result<Game, PgnError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_done {
+            return None;
+        }
+
+        match self.next_game_text()? {
+            Ok((tag_lines, movetext)) => {
+                self.games_read += 1;
+
+                Some(parse_game(self.games_read, &tag_lines, &movetext))
+            }
+            Err(error) => {
+                self.is_done = true;
+
+                Some(Err(PgnError::Io(error)))
+            }
+        }
+    }
+}
+
+// Whether a `{` comment is still open at the end of `line`
+fn ends_in_comment(line: &str, mut in_comment: bool) -> bool {
+    for chr in line.chars() {
+        match chr {
+            '}' if in_comment => in_comment = false,
+            '{' if !in_comment => in_comment = true,
+            // The rest of the line is a comment, and braces in it don't count
+            ';' if !in_comment => break,
+            _ => {}
+        }
+    }
+
+    in_comment
+}
+
+fn parse_game(game: usize, tag_lines: &[String], movetext: &str) -> Result<Game, PgnError> {
+    let tags = tag_lines
+        .iter()
+        .map(|line| {
+            parse_tag(line).ok_or_else(|| PgnError::InvalidTag {
+                game,
+                line: line.to_owned(),
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let fen = tags
+        .iter()
+        .find(|(name, _)| name == "FEN")
+        .map_or(STARTING_FEN, |(_, value)| value.as_str());
+
+    let starting_board = Board::from_fen(fen).map_err(|_| PgnError::InvalidFen {
+        game,
+        fen: fen.to_owned(),
+    })?;
+
+    let mut parser = MovetextParser {
+        game,
+        tokens: Tokens { rest: movetext },
+        result: None,
+    };
+
+    let mainline = parser.parse_variation(&starting_board, 0)?;
+
+    // The marker at the end of the movetext wins over the tag
+    let result = parser
+        .result
+        .or_else(|| {
+            tags.iter()
+                .find(|(name, _)| name == "Result")
+                .and_then(|(_, value)| GameResult::from(value))
+        })
+        .unwrap_or(GameResult::Unfinished);
+
+    Ok(Game {
+        tags,
+        starting_board,
+        mainline,
+        result,
+    })
+}
+
+// `[Name "Value"]`, where the value may escape `"` and `\` with a `\`
+fn parse_tag(line: &str) -> Option<(String, String)> {
+    if !line.starts_with('[') || !line.ends_with(']') {
+        return None;
+    }
+
+    let inner = line[1..line.len() - 1].trim();
+    let name_end = inner.find(char::is_whitespace)?;
+    let quoted = inner[name_end..].trim();
+
+    if quoted.len() < 2 || !quoted.starts_with('"') || !quoted.ends_with('"') {
+        return None;
+    }
+
+    let mut value = String::new();
+    let mut chars = quoted[1..quoted.len() - 1].chars();
+
+    while let Some(chr) = chars.next() {
+        match chr {
+            '\\' => value.push(chars.next()?),
+            '"' => return None,
+            _ => value.push(chr),
+        }
+    }
+
+    Some((inner[..name_end].to_owned(), value))
+}
+
+enum Token<'a> {
+    Comment(String),
+    OpenVariation,
+    CloseVariation,
+    // Moves, move numbers, NAGs and results
+    Word(&'a str),
+    UnterminatedComment,
+}
+
+struct Tokens<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = self.rest.trim_start();
+
+        let (token, length) = match rest.chars().next()? {
+            '{' => match rest.find('}') {
+                Some(end) => (Token::Comment(normalize_comment(&rest[1..end])), end + 1),
+                None => (Token::UnterminatedComment, rest.len()),
+            },
+            ';' => {
+                let end = rest.find('\n').unwrap_or(rest.len());
+
+                (Token::Comment(normalize_comment(&rest[1..end])), end)
+            }
+            '(' => (Token::OpenVariation, 1),
+            ')' => (Token::CloseVariation, 1),
+            _ => {
+                let end = rest
+                    .find(|chr: char| chr.is_whitespace() || "{};()".contains(chr))
+                    .unwrap_or(rest.len());
+
+                (Token::Word(&rest[..end]), end)
+            }
+        };
+
+        self.rest = &rest[length..];
+
+        Some(token)
+    }
+}
+
+// Comments that span lines come out on one
+fn normalize_comment(comment: &str) -> String {
+    comment.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+struct MovetextParser<'a> {
+    game: usize,
+    tokens: Tokens<'a>,
+    result: Option<GameResult>,
+}
+
+impl<'a> MovetextParser<'a> {
+    // Reads moves played from `board` up to the `)` closing this variation,
+    //  or for the mainline, up to the result
+    fn parse_variation(&mut self, board: &Board, depth: usize) -> Result<Variation, PgnError> {
+        let mut variation = Variation::default();
+        let mut board = board.clone();
+
+        // Where the last move was played from, which is where any
+        //  alternatives to it start
+        let mut previous_board: Option<Board> = None;
+
+        while let Some(token) = self.tokens.next() {
+            let word = match token {
+                Token::Comment(comment) => {
+                    match variation.moves.last_mut() {
+                        Some(last_move) => last_move.comments.push(comment),
+                        None => variation.comments.push(comment),
+                    }
+
+                    continue;
+                }
+                Token::OpenVariation => {
+                    let before = match previous_board.as_ref() {
+                        Some(before) => before,
+                        None => return Err(self.unexpected("(")),
+                    };
+                    let alternative = self.parse_variation(before, depth + 1)?;
+
+                    if let Some(last_move) = variation.moves.last_mut() {
+                        last_move.variations.push(alternative);
+                    }
+
+                    continue;
+                }
+                Token::CloseVariation if depth > 0 => return Ok(variation),
+                Token::CloseVariation => return Err(self.unexpected(")")),
+                Token::UnterminatedComment => {
+                    return Err(PgnError::Unterminated { game: self.game })
+                }
+                Token::Word(word) => word,
+            };
+
+            if let Some(number) = word.strip_prefix('$') {
+                let nag = number.parse().map_err(|_| self.unexpected(word))?;
+
+                match variation.moves.last_mut() {
+                    Some(last_move) => last_move.nags.push(nag),
+                    None => return Err(self.unexpected(word)),
+                }
+
+                continue;
+            }
+
+            if let Some(result) = GameResult::from(word) {
+                if depth > 0 {
+                    return Err(self.unexpected(word));
+                }
+
+                self.result = Some(result);
+                return Ok(variation);
+            }
+
+            let san = strip_move_number(word);
+
+            // `e.p.` is sometimes written apart from its move
+            if san.is_empty() || san == "e.p." {
+                continue;
+            }
+
+            let (san, nag) = split_annotation(san).ok_or_else(|| self.unexpected(word))?;
+
+            let mv = board
+                .parse_san(san)
+                .map_err(|error| PgnError::InvalidMove {
+                    game: self.game,
+                    error,
+                })?;
+
+            previous_board = Some(board.clone());
+            board.make_move(&mv);
+
+            variation.moves.push(PgnMove {
+                mv,
+                nags: nag.into_iter().collect(),
+                comments: vec![],
+                variations: vec![],
+            });
+        }
+
+        if depth > 0 {
+            Err(PgnError::Unterminated { game: self.game })
+        } else {
+            Ok(variation)
+        }
+    }
+
+    fn unexpected(&self, token: &str) -> PgnError {
+        PgnError::UnexpectedToken {
+            game: self.game,
+            token: token.to_owned(),
+        }
+    }
+}
+
+// `12.`, `12...` and `12.e4` all number the move, and only the last has a
+//  move left over
+fn strip_move_number(word: &str) -> &str {
+    let after_digits = word.trim_start_matches(|chr: char| chr.is_ascii_digit());
+
+    if after_digits.len() < word.len() && after_digits.starts_with('.') {
+        after_digits.trim_start_matches('.')
+    } else {
+        word
+    }
+}
+
+// Splits the `!` and `?` marks off a move and returns the NAG they stand for
+fn split_annotation(word: &str) -> Option<(&str, Option<u8>)> {
+    let san = word.trim_end_matches(&['!', '?'][..]);
+
+    let nag = match &word[san.len()..] {
+        "" => None,
+        "!" => Some(1),
+        "?" => Some(2),
+        "!!" => Some(3),
+        "??" => Some(4),
+        "!?" => Some(5),
+        "?!" => Some(6),
+        _ => return None,
+    };
+
+    Some((san, nag))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess::{MoveType, PieceType, RankFile};
+
+    fn read(pgn: &str) -> Vec<Result<Game, PgnError>> {
+        PgnReader::new(pgn.as_bytes()).collect()
+    }
+
+    const TWO_GAMES: &str = r#"[Event "Casual \"blitz\""]
+[White "Alice"]
+[Black "Bob"]
+[Result "1-0"]
+
+{Opening} 1. e4 e5 2. Nf3 $1 Nc6 {Developing} (2... d6 3. d4 (3. Bc4) 3... Nf6)
+3. Bb5!? a6 4. Ba4 Nf6 5. O-O 1-0
+
+[Event "Second"]
+[Result "*"]
+
+1.d4 ; rest of the line {is ignored
+d5 2.c4 *
+"#;
+
+    #[test]
+    fn test_reads_games() {
+        let games = read(TWO_GAMES);
+        assert_eq!(games.len(), 2);
+
+        let game = games[0].as_ref().unwrap();
+        assert_eq!(game.tag("Event"), Some("Casual \"blitz\""));
+        assert_eq!(game.tag("White"), Some("Alice"));
+        assert_eq!(game.tag("Round"), None);
+        assert_eq!(game.result, GameResult::WhiteWins);
+
+        let mainline = &game.mainline;
+        assert_eq!(mainline.comments, vec!["Opening".to_owned()]);
+        assert_eq!(mainline.moves.len(), 9);
+        assert_eq!(mainline.moves[0].mv.to, RankFile::E4);
+        assert_eq!(mainline.moves[2].nags, vec![1]);
+        assert_eq!(mainline.moves[3].comments, vec!["Developing".to_owned()]);
+        assert_eq!(mainline.moves[4].nags, vec![5]);
+        assert_eq!(
+            mainline.moves[8].mv.move_type,
+            MoveType::Castling {
+                is_queenside: false
+            }
+        );
+
+        // 2... d6 replaces 2... Nc6, and 3. Bc4 replaces 3. d4 inside it
+        let variation = &mainline.moves[3].variations[0];
+        assert_eq!(variation.moves.len(), 3);
+        assert_eq!(variation.moves[0].mv.to, RankFile::D6);
+        assert_eq!(
+            variation.moves[1].variations[0].moves[0].mv.to,
+            RankFile::C4
+        );
+        assert_eq!(variation.moves[2].mv.piece_type, PieceType::Knight);
+
+        let game = games[1].as_ref().unwrap();
+        assert_eq!(game.result, GameResult::Unfinished);
+        assert_eq!(game.mainline.moves.len(), 3);
+        assert_eq!(
+            game.mainline.moves[0].comments,
+            vec!["rest of the line {is ignored".to_owned()]
+        );
+    }
+
+    #[test]
+    fn test_skips_bad_games() {
+        let pgn = "[Event \"Bad\"]\n\n1. e4 e5 2. Ke3 *\n\n\
+                   [Event \"Unclosed\"]\n\n1. e4 (1. d4 *\n\n\
+                   [Event \"Good\"]\n\n1. d4 d5 *\n\n\
+                   [Event \"Bad tag]\n\n*\n";
+        let games = read(pgn);
+
+        assert_eq!(games.len(), 4);
+
+        match &games[0] {
+            Err(PgnError::InvalidMove { game, .. }) => assert_eq!(*game, 1),
+            other => panic!("{:?}", other),
+        }
+
+        match &games[1] {
+            Err(PgnError::UnexpectedToken { game, token }) => {
+                assert_eq!((*game, token.as_str()), (2, "*"))
+            }
+            other => panic!("{:?}", other),
+        }
+
+        assert_eq!(games[2].as_ref().unwrap().mainline.moves.len(), 2);
+
+        match &games[3] {
+            Err(PgnError::InvalidTag { game, .. }) => assert_eq!(*game, 4),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_fen_tag_and_comments_across_lines() {
+        let pgn = "[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/4P3/4K3 w - - 0 1\"]\n\n\
+                   1. e4 {a comment\n[that looks like a tag]} Kd7 0-1\n\
+                   % an escaped line\n";
+        let games = read(pgn);
+
+        assert_eq!(games.len(), 1);
+
+        let game = games[0].as_ref().unwrap();
+        assert_eq!(
+            game.starting_board.to_fen(),
+            "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1"
+        );
+        assert_eq!(game.result, GameResult::BlackWins);
+        assert_eq!(
+            game.mainline.moves[0].comments,
+            vec!["a comment [that looks like a tag]".to_owned()]
+        );
+        assert_eq!(game.mainline.moves[1].mv.to, RankFile::D7);
+    }
+
+    #[test]
+    fn test_unterminated() {
+        match &read("[Event \"?\"]\n\n1. e4 {never closed\n")[0] {
+            Err(PgnError::Unterminated { game }) => assert_eq!(*game, 1),
+            other => panic!("{:?}", other),
+        }
+    }
+}
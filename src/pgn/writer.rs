@@ -0,0 +1,307 @@
+use std::io::{self, Write};
+
+use crate::chess::{Board, Player, STARTING_FEN};
+use crate::pgn::{Game, Variation};
+
+const LINE_WIDTH: usize = 80;
+
+// The Seven Tag Roster, which every game gets in this order ahead of any
+//  other tags, with the value used when a game doesn't have one
+const SEVEN_TAG_ROSTER: [(&str, &str); 7] = [
+    ("Event", "?"),
+    ("Site", "?"),
+    ("Date", "????.??.??"),
+    ("Round", "?"),
+    ("White", "?"),
+    ("Black", "?"),
+    ("Result", "*"),
+];
+
+/// Writes games in the PGN export format, each followed by a blank line so
+///  that the output can be read back with `PgnReader`.
+pub struct PgnWriter<W: Write> {
+    output: W,
+}
+
+impl<W: Write> PgnWriter<W> {
+    pub fn new(output: W) -> Self {
+        PgnWriter { output }
+    }
+
+    pub fn write_game(&mut self, game: &Game) -> io::Result<()> {
+        for (name, value) in tags_for(game) {
+            writeln!(self.output, "[{} \"{}\"]", name, escape_tag_value(&value))?;
+        }
+
+        writeln!(self.output)?;
+
+        let mut tokens = vec![];
+        push_variation(&mut tokens, &game.starting_board, &game.mainline);
+        tokens.push(game.result.to_string());
+
+        for line in wrap(&tokens) {
+            writeln!(self.output, "{}", line)?;
+        }
+
+        writeln!(self.output)
+    }
+
+    pub fn into_inner(self) -> W {
+        self.output
+    }
+}
+
+fn tags_for(game: &Game) -> Vec<(String, String)> {
+    let mut tags: Vec<(String, String)> = SEVEN_TAG_ROSTER
+        .iter()
+        .map(|&(name, default)| {
+            let value = match name {
+                // The result has to agree with the movetext
+                "Result" => game.result.to_string(),
+                _ => game.tag(name).unwrap_or(default).to_owned(),
+            };
+
+            (name.to_owned(), value)
+        })
+        .collect();
+
+    tags.extend(
+        game.tags
+            .iter()
+            .filter(|(name, _)| SEVEN_TAG_ROSTER.iter().all(|&(roster, _)| roster != name))
+            .cloned(),
+    );
+
+    let fen = game.starting_board.to_fen();
+
+    if fen != STARTING_FEN && game.tag("FEN").is_none() {
+        tags.push(("SetUp".to_owned(), "1".to_owned()));
+        tags.push(("FEN".to_owned(), fen));
+    }
+
+    tags
+}
+
+fn escape_tag_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+// Turns a variation played from `board` into the tokens that line wrapping
+//  can break between.  A move stays together with its number, while comments
+//  are split into words.
+fn push_variation(tokens: &mut Vec<String>, board: &Board, variation: &Variation) {
+    for comment in variation.comments.iter() {
+        push_comment(tokens, comment);
+    }
+
+    let mut board = board.clone();
+    // Black's moves only need a number after something that interrupts the
+    //  movetext, or at the start
+    let mut needs_number = true;
+
+    for pgn_move in variation.moves.iter() {
+        let san = board.to_san(&pgn_move.mv);
+
+        if board.next_player == Player::White {
+            tokens.push(format!("{}. {}", board.fullmove_number, san));
+        } else if needs_number {
+            tokens.push(format!("{}... {}", board.fullmove_number, san));
+        } else {
+            tokens.push(san);
+        }
+
+        for nag in pgn_move.nags.iter() {
+            tokens.push(format!("${}", nag));
+        }
+
+        for comment in pgn_move.comments.iter() {
+            push_comment(tokens, comment);
+        }
+
+        for alternative in pgn_move.variations.iter() {
+            let mut alternative_tokens = vec![];
+            push_variation(&mut alternative_tokens, &board, alternative);
+
+            match alternative_tokens.len() {
+                0 => tokens.push("()".to_owned()),
+                len => {
+                    alternative_tokens[0].insert(0, '(');
+                    alternative_tokens[len - 1].push(')');
+                    tokens.extend(alternative_tokens);
+                }
+            }
+        }
+
+        needs_number = !pgn_move.comments.is_empty() || !pgn_move.variations.is_empty();
+
+        board.make_move(&pgn_move.mv);
+    }
+}
+
+// There's no escaping a `}` inside a comment, so any are left out rather
+//  than ending the comment early
+fn push_comment(tokens: &mut Vec<String>, comment: &str) {
+    let comment = format!("{{{}}}", comment.replace('}', ""));
+
+    tokens.extend(comment.split(' ').map(|word| word.to_owned()));
+}
+
+// Joins the tokens with spaces, starting a new line whenever the next one
+//  would run past `LINE_WIDTH`
+fn wrap(tokens: &[String]) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = String::new();
+
+    for token in tokens.iter() {
+        if !line.is_empty() && line.len() + 1 + token.len() > LINE_WIDTH {
+            lines.push(line);
+            line = String::new();
+        }
+
+        if !line.is_empty() {
+            line.push(' ');
+        }
+
+        line.push_str(token);
+    }
+
+    if !line.is_empty() {
+        lines.push(line);
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess::Move;
+    use crate::pgn::{GameResult, PgnReader};
+
+    fn write(games: &[Game]) -> String {
+        let mut writer = PgnWriter::new(vec![]);
+
+        for game in games.iter() {
+            writer.write_game(game).unwrap();
+        }
+
+        String::from_utf8(writer.into_inner()).unwrap()
+    }
+
+    fn read(pgn: &str) -> Vec<Game> {
+        PgnReader::new(pgn.as_bytes())
+            .map(|game| game.unwrap())
+            .collect()
+    }
+
+    // Plays out the first legal move each turn, which is enough to fill a
+    //  few lines of movetext
+    fn first_moves(board: &Board, plies: usize) -> Vec<Move> {
+        let mut board = board.clone();
+        let mut moves = vec![];
+
+        for _ in 0..plies {
+            let mv = match board.legal_moves().into_iter().next() {
+                Some(mv) => mv,
+                None => break,
+            };
+
+            board.make_move(&mv);
+            moves.push(mv);
+        }
+
+        moves
+    }
+
+    #[test]
+    fn test_writes_seven_tag_roster() {
+        let board = Board::from_fen(STARTING_FEN).unwrap();
+        let mut game = Game::from_moves(board.clone(), &first_moves(&board, 4));
+        game.tags.push(("White".to_owned(), "chess_ai".to_owned()));
+        game.tags
+            .push(("Annotator".to_owned(), "A \"quoted\" name".to_owned()));
+
+        let pgn = write(&[game]);
+        let lines: Vec<&str> = pgn.lines().collect();
+
+        assert_eq!(
+            &lines[..9],
+            &[
+                "[Event \"?\"]",
+                "[Site \"?\"]",
+                "[Date \"????.??.??\"]",
+                "[Round \"?\"]",
+                "[White \"chess_ai\"]",
+                "[Black \"?\"]",
+                "[Result \"*\"]",
+                "[Annotator \"A \\\"quoted\\\" name\"]",
+                "",
+            ]
+        );
+        assert_eq!(lines[9], "1. a3 a5 2. b3 a4 *");
+    }
+
+    #[test]
+    fn test_round_trips_engine_games() {
+        for &fen in [
+            STARTING_FEN,
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R b KQkq - 0 1",
+        ]
+        .iter()
+        {
+            let board = Board::from_fen(fen).unwrap();
+            let moves = first_moves(&board, 120);
+            let mut game = Game::from_moves(board, &moves);
+            game.result = GameResult::Draw;
+
+            let pgn = write(&[game.clone(), game.clone()]);
+            assert!(pgn.lines().all(|line| line.len() <= LINE_WIDTH), "{}", pgn);
+
+            let games = read(&pgn);
+            assert_eq!(games.len(), 2);
+
+            for read_back in games.iter() {
+                assert_eq!(read_back.starting_board.to_fen(), fen);
+                assert_eq!(read_back.mainline, game.mainline);
+                assert_eq!(read_back.result, GameResult::Draw);
+                assert_eq!(read_back.tag("Result"), Some("1/2-1/2"));
+            }
+        }
+    }
+
+    #[test]
+    fn test_round_trips_comments_and_variations() {
+        let pgn = "[Event \"Annotated\"]\n[Result \"1-0\"]\n\n\
+                   {Opening} 1. e4 e5 2. Nf3 $1 Nc6 {A long comment that has to be \
+                   wrapped over more than one line of the movetext to fit} \
+                   (2... d6 3. d4 (3. Bc4) 3... Nf6) 3. Bb5 $5 a6 1-0\n";
+        let mut game = read(pgn).remove(0);
+        // Written without its closing brace, which would end the comment
+        game.mainline.moves[0]
+            .comments
+            .push("{+0.30} at depth 12".to_owned());
+
+        let written = write(std::slice::from_ref(&game));
+        let lines: Vec<&str> = written.lines().collect();
+        assert!(lines.iter().all(|line| line.len() <= LINE_WIDTH));
+        // Moves aren't split from their numbers
+        assert!(lines.iter().all(|line| !line.ends_with('.')));
+
+        let movetext = lines[8..].join(" ").trim_end().to_owned();
+        assert!(movetext.starts_with(
+            "{Opening} 1. e4 {{+0.30 at depth 12} 1... e5 2. Nf3 $1 Nc6 {A long comment"
+        ));
+        assert!(movetext.ends_with("(2... d6 3. d4 (3. Bc4) 3... Nf6) 3. Bb5 $5 a6 1-0"));
+
+        let mut read_back = read(&written).remove(0);
+        assert_eq!(
+            read_back.mainline.moves[0].comments,
+            vec!["{+0.30 at depth 12"]
+        );
+
+        read_back.mainline.moves[0].comments = game.mainline.moves[0].comments.clone();
+        assert_eq!(read_back.mainline, game.mainline);
+        assert_eq!(read_back.tag("Event"), Some("Annotated"));
+        assert_eq!(read_back.result, GameResult::WhiteWins);
+    }
+}
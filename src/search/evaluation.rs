@@ -0,0 +1,44 @@
+use crate::chess::{Board, PieceType, PieceValues};
+
+// The kings always cancel out, and test boards may not have both
+const MATERIAL_PIECES: [PieceType; 5] = [
+    PieceType::Pawn,
+    PieceType::Rook,
+    PieceType::Knight,
+    PieceType::Bishop,
+    PieceType::Queen,
+];
+
+/// How far ahead the player to move is in material, in centipawns.
+pub fn evaluate(board: &Board, values: &PieceValues) -> i32 {
+    let ours = board.players[board.next_player as usize];
+    let theirs = board.players[board.next_player.opponent() as usize];
+
+    MATERIAL_PIECES
+        .iter()
+        .map(|&piece_type| {
+            let pieces = board.pieces[piece_type as usize];
+            let difference = pieces.intersect(ours).count_pieces() as i32
+                - pieces.intersect(theirs).count_pieces() as i32;
+
+            difference * values.value(piece_type)
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess::STARTING_FEN;
+
+    #[test]
+    fn test_material() {
+        let values = PieceValues::default();
+        let evaluate_fen = |fen: &str| evaluate(&Board::from_fen(fen).unwrap(), &values);
+
+        assert_eq!(evaluate_fen(STARTING_FEN), 0);
+        assert_eq!(evaluate_fen("4k3/8/8/8/8/8/3Q4/RN2K3 w - - 0 1"), 1700);
+        assert_eq!(evaluate_fen("4k3/8/8/8/8/8/3Q4/RN2K3 b - - 0 1"), -1700);
+        assert_eq!(evaluate_fen("4k3/pp6/8/8/8/8/8/1B2K3 w - - 0 1"), 100);
+    }
+}
@@ -0,0 +1,244 @@
+use std::time::Instant;
+
+use super::{
+    evaluate, mate_distance, SearchInfo, SearchLimits, SearchResult, Searcher, INFINITY, MAX_PLY,
+};
+use crate::chess::Board;
+
+impl Searcher {
+    /// Searches one ply deeper at a time until one of `limits` is hit or the
+    ///  stop flag is set, calling `on_info` after each depth it finishes.
+    ///  Stopping throws away the unfinished depth, so the result is always
+    ///  from the last finished one.  The first depth is always finished, so
+    ///  there's a move to play whenever the position has one.
+    pub fn search_with_limits<F>(
+        &mut self,
+        board: &Board,
+        limits: &SearchLimits,
+        mut on_info: F,
+    ) -> SearchResult
+    where
+        F: FnMut(&SearchInfo),
+    {
+        let start = Instant::now();
+        let mut board = board.clone();
+
+        let max_depth = match limits.depth {
+            Some(depth) if !limits.infinite => depth.min(MAX_PLY),
+            _ => MAX_PLY,
+        };
+
+        self.start_search(
+            limits
+                .time_budget(board.next_player)
+                .map(|budget| start + budget),
+            if limits.infinite { None } else { limits.nodes },
+        );
+
+        let mut result = SearchResult {
+            best_move: None,
+            score: evaluate(&board, &self.values),
+            principal_variation: vec![],
+            depth: 0,
+            nodes: 0,
+        };
+
+        for depth in 1..=max_depth {
+            let mut principal_variation = vec![];
+            let score = self.negamax(
+                &mut board,
+                depth,
+                0,
+                -INFINITY,
+                INFINITY,
+                &mut principal_variation,
+            );
+
+            if self.is_stopped {
+                break;
+            }
+
+            self.can_stop = true;
+
+            on_info(&SearchInfo {
+                depth,
+                score,
+                principal_variation: principal_variation.clone(),
+                nodes: self.nodes,
+                elapsed: start.elapsed(),
+            });
+
+            result = SearchResult {
+                best_move: principal_variation.first().cloned(),
+                score,
+                principal_variation,
+                depth,
+                nodes: self.nodes,
+            };
+
+            if result.best_move.is_none() || self.should_stop() {
+                break;
+            }
+
+            // Searching deeper can't find a quicker mate than one that's
+            //  already been seen to the end
+            let is_mate_found = match mate_distance(score) {
+                Some(distance) => distance.unsigned_abs() <= depth,
+                None => false,
+            };
+
+            if is_mate_found && !limits.infinite {
+                break;
+            }
+
+            if matches!(self.deadline, Some(deadline) if Instant::now() >= deadline) {
+                break;
+            }
+        }
+
+        result.nodes = self.nodes;
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::chess::STARTING_FEN;
+    use crate::search::MATE_SCORE;
+
+    fn kiwipete() -> Board {
+        Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")
+            .unwrap()
+    }
+
+    #[test]
+    fn test_reports_each_depth() {
+        let board = kiwipete();
+        let mut depths = vec![];
+
+        let result = Searcher::new().search_with_limits(&board, &SearchLimits::depth(3), |info| {
+            depths.push(info.depth);
+        });
+
+        let fixed_depth = Searcher::new().search(&board, 3);
+
+        assert_eq!(depths, vec![1, 2, 3]);
+        assert_eq!(result.depth, 3);
+        // Ties may be broken differently once the moves get reordered
+        assert_eq!(result.score, fixed_depth.score);
+        assert!(result.best_move.is_some());
+    }
+
+    #[test]
+    fn test_node_limit() {
+        let board = Board::from_fen(STARTING_FEN).unwrap();
+        let limits = SearchLimits {
+            nodes: Some(2_000),
+            ..SearchLimits::default()
+        };
+
+        let result = Searcher::new().search_with_limits(&board, &limits, |_| {});
+
+        assert!(result.best_move.is_some());
+        assert!(result.depth >= 1);
+        assert!(result.nodes <= 2_000);
+    }
+
+    #[test]
+    fn test_reuse_after_stopping() {
+        let board = Board::from_fen(STARTING_FEN).unwrap();
+        let limits = SearchLimits {
+            nodes: Some(500),
+            ..SearchLimits::default()
+        };
+        let mut searcher = Searcher::new();
+
+        searcher.search_with_limits(&board, &limits, |_| {});
+        let result = searcher.search(&board, 3);
+
+        assert_eq!(result.depth, 3);
+        assert!(result.best_move.is_some());
+        assert!(result.nodes > 1);
+    }
+
+    #[test]
+    fn test_movetime() {
+        let board = kiwipete();
+        let limits = SearchLimits {
+            movetime: Some(Duration::from_millis(100)),
+            ..SearchLimits::default()
+        };
+
+        let start = Instant::now();
+        let result = Searcher::new().search_with_limits(&board, &limits, |_| {});
+
+        assert!(result.best_move.is_some());
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_stop_flag() {
+        let board = kiwipete();
+        let limits = SearchLimits {
+            infinite: true,
+            ..SearchLimits::default()
+        };
+
+        // Already set, so only the first depth gets searched
+        let stop = Arc::new(AtomicBool::new(true));
+        let result = Searcher::with_stop_flag(stop).search_with_limits(&board, &limits, |_| {});
+
+        assert_eq!(result.depth, 1);
+        assert!(result.best_move.is_some());
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopper = {
+            let stop = stop.clone();
+
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(100));
+                stop.store(true, Ordering::Relaxed);
+            })
+        };
+
+        let mut last_info = None;
+        let result = Searcher::with_stop_flag(stop).search_with_limits(&board, &limits, |info| {
+            last_info = Some(info.clone());
+        });
+        stopper.join().unwrap();
+
+        let last_info = last_info.unwrap();
+        assert_eq!(result.depth, last_info.depth);
+        assert_eq!(result.principal_variation, last_info.principal_variation);
+        assert!(result.best_move.is_some());
+    }
+
+    #[test]
+    fn test_fixed_depth_ignores_stop_flag() {
+        let board = kiwipete();
+        let stop = Arc::new(AtomicBool::new(true));
+
+        let stopped = Searcher::with_stop_flag(stop).search(&board, 3);
+        let unstopped = Searcher::new().search(&board, 3);
+
+        assert_eq!(stopped.depth, 3);
+        assert_eq!(stopped.score, unstopped.score);
+        assert_eq!(stopped.nodes, unstopped.nodes);
+    }
+
+    #[test]
+    fn test_stops_at_forced_mate() {
+        let board = Board::from_fen("7k/8/8/8/8/8/R7/1R4K1 w - - 0 1").unwrap();
+        let result = Searcher::new().search_with_limits(&board, &SearchLimits::default(), |_| {});
+
+        assert_eq!(result.depth, 3);
+        assert_eq!(result.score, MATE_SCORE - 3);
+    }
+}
@@ -0,0 +1,106 @@
+use std::time::Duration;
+
+use crate::chess::Player;
+
+// Spend the clock as though this many moves were left to play
+const EXPECTED_MOVES_LEFT: u32 = 30;
+
+/// When `Searcher::search_with_limits` should stop, as a UCI `go` command
+///  would give them.  Whichever limit is hit first wins, and leaving all of
+///  them unset searches until `MAX_PLY` or a forced mate.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchLimits {
+    pub depth: Option<u32>,
+    pub nodes: Option<u64>,
+    /// Exactly how long to search, overriding the clocks
+    pub movetime: Option<Duration>,
+    pub wtime: Option<Duration>,
+    pub btime: Option<Duration>,
+    pub winc: Option<Duration>,
+    pub binc: Option<Duration>,
+    /// Ignore every other limit and search until the stop flag is set
+    pub infinite: bool,
+}
+
+impl SearchLimits {
+    pub fn depth(depth: u32) -> Self {
+        SearchLimits {
+            depth: Some(depth),
+            ..SearchLimits::default()
+        }
+    }
+
+    /// How long `player` can spend on this move, if there's a limit.
+    pub fn time_budget(&self, player: Player) -> Option<Duration> {
+        if self.infinite {
+            return None;
+        }
+
+        if let Some(movetime) = self.movetime {
+            return Some(movetime);
+        }
+
+        let (remaining, increment) = match player {
+            Player::White => (self.wtime?, self.winc),
+            Player::Black => (self.btime?, self.binc),
+        };
+
+        let budget = remaining / EXPECTED_MOVES_LEFT + increment.unwrap_or_default();
+
+        // Never bet more than half the clock on the increment coming back
+        Some(budget.min(remaining / 2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_budget() {
+        let clocks = SearchLimits {
+            wtime: Some(Duration::from_secs(60)),
+            btime: Some(Duration::from_secs(30)),
+            winc: Some(Duration::from_secs(1)),
+            ..SearchLimits::default()
+        };
+
+        assert_eq!(
+            clocks.time_budget(Player::White),
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(
+            clocks.time_budget(Player::Black),
+            Some(Duration::from_secs(1))
+        );
+
+        let low_on_time = SearchLimits {
+            wtime: Some(Duration::from_secs(2)),
+            winc: Some(Duration::from_secs(5)),
+            ..SearchLimits::default()
+        };
+
+        assert_eq!(
+            low_on_time.time_budget(Player::White),
+            Some(Duration::from_secs(1))
+        );
+
+        let movetime = SearchLimits {
+            movetime: Some(Duration::from_millis(500)),
+            ..clocks.clone()
+        };
+
+        assert_eq!(
+            movetime.time_budget(Player::Black),
+            Some(Duration::from_millis(500))
+        );
+
+        let infinite = SearchLimits {
+            infinite: true,
+            ..movetime
+        };
+
+        assert_eq!(infinite.time_budget(Player::White), None);
+        assert_eq!(SearchLimits::depth(4).time_budget(Player::White), None);
+    }
+}
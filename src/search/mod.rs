@@ -0,0 +1,449 @@
+mod evaluation;
+mod iterative_deepening;
+mod limits;
+mod quiescence;
+mod transposition;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::chess::{Board, GenType, Move, PieceValues};
+
+pub use crate::search::evaluation::evaluate;
+pub use crate::search::limits::SearchLimits;
+pub use crate::search::transposition::{Bound, TableEntry, TranspositionTable};
+
+/// The score for giving mate right now.  Mates further away score one less
+///  for every ply it takes to reach them, so the quickest one wins out.
+pub const MATE_SCORE: i32 = 30_000;
+/// No search goes deeper than this, which keeps every mate score above
+///  `MATE_SCORE - MAX_PLY`.
+pub const MAX_PLY: u32 = 128;
+pub const DRAW_SCORE: i32 = 0;
+
+// Above every score a search can return, to start the window with
+const INFINITY: i32 = MATE_SCORE + 1;
+// Reading the clock every node would cost more than it's worth.  Kept a
+//  power of two so the check is a mask
+const TIME_CHECK_INTERVAL: u64 = 1024;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    /// `None` only when there was nothing to play, or the depth was 0
+    pub best_move: Option<Move>,
+    /// In centipawns for the player to move, see `MATE_SCORE` for mates
+    pub score: i32,
+    /// The moves both players are expected to play, starting with
+    ///  `best_move`
+    pub principal_variation: Vec<Move>,
+    /// How many plies deep the search that found this went
+    pub depth: u32,
+    pub nodes: u64,
+}
+
+/// What the search found at one depth, reported as soon as that depth is
+///  finished.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchInfo {
+    pub depth: u32,
+    pub score: i32,
+    pub principal_variation: Vec<Move>,
+    /// Counted from the start of the whole search, not just this depth
+    pub nodes: u64,
+    pub elapsed: Duration,
+}
+
+/// Whether `score` means one side can force mate.
+pub fn is_mate_score(score: i32) -> bool {
+    score.abs() > MATE_SCORE - MAX_PLY as i32
+}
+
+/// How many plies away the mate behind `score` is, positive when the
+///  player to move is the one giving it.
+pub fn mate_distance(score: i32) -> Option<i32> {
+    if !is_mate_score(score) {
+        return None;
+    }
+
+    Some(score.signum() * (MATE_SCORE - score.abs()))
+}
+
+/// A negamax search with alpha-beta pruning, carried on through captures
+///  at the leaves.  It plays the same moves `Board::generate_moves` yields
+///  as child boards, but makes and unmakes them in place rather than
+///  cloning the board for each one.
+#[derive(Debug)]
+pub struct Searcher {
+    pub values: PieceValues,
+    /// Kept from one search to the next, use `resize` on it to change
+    ///  how much memory it takes
+    pub transposition_table: TranspositionTable,
+    /// Whether the quiescence search skips captures that lose material
+    ///  by `Board::see`.  On by default.
+    pub see_pruning: bool,
+    nodes: u64,
+
+    // Set from outside to stop the search early.  Never cleared here.
+    stop: Arc<AtomicBool>,
+    // The limits below only apply while this is set, so that a search
+    //  always has at least one finished depth to fall back on
+    can_stop: bool,
+    is_stopped: bool,
+    deadline: Option<Instant>,
+    max_nodes: Option<u64>,
+}
+
+impl Default for Searcher {
+    fn default() -> Self {
+        Searcher {
+            values: PieceValues::default(),
+            transposition_table: TranspositionTable::default(),
+            see_pruning: true,
+            nodes: 0,
+
+            stop: Arc::default(),
+            can_stop: false,
+            is_stopped: false,
+            deadline: None,
+            max_nodes: None,
+        }
+    }
+}
+
+impl Searcher {
+    pub fn new() -> Self {
+        Searcher::default()
+    }
+
+    /// A searcher that stops once `stop` is set, e.g. by another thread
+    ///  handling a UCI `stop`.  Only `search_with_limits` reads the flag,
+    ///  and only after its first depth is finished, so a fixed-depth
+    ///  `search` always runs to the end.
+    pub fn with_stop_flag(stop: Arc<AtomicBool>) -> Self {
+        Searcher {
+            stop,
+            ..Searcher::default()
+        }
+    }
+
+    /// Looks `depth` plies ahead of `board`, which is capped at `MAX_PLY`.
+    pub fn search(&mut self, board: &Board, depth: u32) -> SearchResult {
+        let mut board = board.clone();
+        let mut principal_variation = vec![];
+        let depth = depth.min(MAX_PLY);
+
+        self.start_search(None, None);
+
+        let score = self.negamax(
+            &mut board,
+            depth,
+            0,
+            -INFINITY,
+            INFINITY,
+            &mut principal_variation,
+        );
+
+        SearchResult {
+            best_move: principal_variation.first().cloned(),
+            score,
+            principal_variation,
+            depth,
+            nodes: self.nodes,
+        }
+    }
+
+    // Clears what's left over from the last search, stopped or not, before
+    //  starting a new one
+    fn start_search(&mut self, deadline: Option<Instant>, max_nodes: Option<u64>) {
+        self.nodes = 0;
+        self.can_stop = false;
+        self.is_stopped = false;
+        self.deadline = deadline;
+        self.max_nodes = max_nodes;
+        self.transposition_table.new_search();
+    }
+
+    // Whether any of the limits has been hit, which leaves every score
+    //  from then on meaningless
+    fn should_stop(&mut self) -> bool {
+        if !self.can_stop || self.is_stopped {
+            return self.is_stopped;
+        }
+
+        let is_out_of_time = self.nodes & (TIME_CHECK_INTERVAL - 1) == 0
+            && matches!(self.deadline, Some(deadline) if Instant::now() >= deadline);
+
+        self.is_stopped = is_out_of_time
+            || matches!(self.max_nodes, Some(max_nodes) if self.nodes >= max_nodes)
+            || self.stop.load(Ordering::Relaxed);
+
+        self.is_stopped
+    }
+
+    // Scores `board` for the player to move, filling `principal_variation`
+    //  with the line that gets there whenever the score lands inside the
+    //  window
+    fn negamax(
+        &mut self,
+        board: &mut Board,
+        depth: u32,
+        ply: u32,
+        mut alpha: i32,
+        beta: i32,
+        principal_variation: &mut Vec<Move>,
+    ) -> i32 {
+        principal_variation.clear();
+
+        if depth == 0 {
+            return self.quiescence(board, ply, alpha, beta);
+        }
+
+        self.nodes += 1;
+
+        if self.should_stop() {
+            return DRAW_SCORE;
+        }
+
+        // Before the table, as the zobrist key leaves out the halfmove clock
+        //  and an entry could hide the draw
+        if ply > 0 && board.is_fifty_move_draw() {
+            return DRAW_SCORE;
+        }
+
+        let alpha_before = alpha;
+        let mut hash_move = None;
+
+        if let Some(entry) = self.transposition_table.probe(board.zobrist_key, ply) {
+            // Checked before generating any moves, as a hit makes them
+            //  unnecessary.  The root always searches, so that there's a
+            //  move to return.
+            let is_usable = ply > 0
+                && entry.depth >= depth
+                && match entry.bound {
+                    Bound::Exact => true,
+                    Bound::Lower => entry.score >= beta,
+                    Bound::Upper => entry.score <= alpha,
+                };
+
+            if is_usable {
+                principal_variation.extend(entry.best_move);
+                return entry.score;
+            }
+
+            hash_move = entry.best_move;
+        }
+
+        let mut moves = board.legal_moves_unannotated(GenType::All);
+
+        if moves.is_empty() {
+            return if board.is_king_attacked(board.next_player) {
+                -MATE_SCORE + ply as i32
+            } else {
+                DRAW_SCORE
+            };
+        }
+
+        // Captures are the likeliest to cause a cutoff, so they go first,
+        //  after the best move from the last time the position was searched
+        moves.sort_by_key(|mv| !mv.is_capture);
+
+        if let Some(index) = hash_move
+            .as_ref()
+            .and_then(|hash_move| moves.iter().position(|mv| mv == hash_move))
+        {
+            moves[..=index].rotate_right(1);
+        }
+
+        let mut best_score = -INFINITY;
+        let mut best_move = None;
+        let mut child_variation = vec![];
+
+        for mv in moves.iter() {
+            let undo = board.make_move(mv);
+            let score = -self.negamax(
+                board,
+                depth - 1,
+                ply + 1,
+                -beta,
+                -alpha,
+                &mut child_variation,
+            );
+            board.unmake_move(mv, undo);
+
+            if self.is_stopped {
+                return DRAW_SCORE;
+            }
+
+            if score > best_score {
+                best_score = score;
+                best_move = Some(mv);
+
+                if score > alpha {
+                    alpha = score;
+
+                    principal_variation.clear();
+                    principal_variation.push(mv.clone());
+                    principal_variation.append(&mut child_variation);
+                }
+            }
+
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        let bound = if best_score <= alpha_before {
+            Bound::Upper
+        } else if best_score >= beta {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+
+        self.transposition_table.store(
+            board.zobrist_key,
+            ply,
+            depth,
+            best_score,
+            bound,
+            best_move.cloned(),
+        );
+
+        best_score
+    }
+}
+
+/// Searches `board` `depth` plies deep with a fresh `Searcher`.
+pub fn search(board: &Board, depth: u32) -> SearchResult {
+    Searcher::new().search(board, depth)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess::STARTING_FEN;
+
+    fn search_fen(fen: &str, depth: u32) -> SearchResult {
+        search(&Board::from_fen(fen).unwrap(), depth)
+    }
+
+    fn san_line(fen: &str, moves: &[Move]) -> Vec<String> {
+        let mut board = Board::from_fen(fen).unwrap();
+
+        moves
+            .iter()
+            .map(|mv| {
+                let san = board.to_san(mv);
+                board.make_move(mv);
+
+                san
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_wins_material() {
+        let fen = "4k3/8/8/3q4/8/8/8/3RK3 w - - 0 1";
+        let result = search_fen(fen, 2);
+
+        assert_eq!(san_line(fen, &result.principal_variation)[0], "Rxd5");
+        assert_eq!(result.score, 500);
+        assert_eq!(result.principal_variation.len(), 2);
+    }
+
+    #[test]
+    fn test_avoids_losing_material() {
+        // The queen takes a pawn defended by a pawn
+        let fen = "4k3/2p5/3p4/8/8/8/8/3QK3 w - - 0 1";
+        let result = search_fen(fen, 2);
+
+        assert_ne!(san_line(fen, &result.principal_variation)[0], "Qxd6");
+        assert_eq!(result.score, 700);
+    }
+
+    #[test]
+    fn test_mate_distances() {
+        // Back rank mate in one
+        let fen = "6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1";
+        let result = search_fen(fen, 3);
+
+        assert_eq!(san_line(fen, &result.principal_variation), vec!["Ra8#"]);
+        assert_eq!(result.score, MATE_SCORE - 1);
+        assert_eq!(mate_distance(result.score), Some(1));
+
+        // Mate in two with the rook ladder
+        let fen = "7k/8/8/8/8/8/R7/1R4K1 w - - 0 1";
+        let result = search_fen(fen, 4);
+
+        assert_eq!(result.score, MATE_SCORE - 3);
+        assert_eq!(mate_distance(result.score), Some(3));
+        assert_eq!(result.principal_variation.len(), 3);
+
+        // Getting mated after the only legal move
+        let fen = "7k/8/6K1/8/8/8/8/R7 b - - 0 1";
+        let result = search_fen(fen, 2);
+
+        assert_eq!(
+            san_line(fen, &result.principal_variation),
+            vec!["Kg8", "Ra8#"]
+        );
+        assert_eq!(result.score, -MATE_SCORE + 2);
+        assert_eq!(mate_distance(result.score), Some(-2));
+
+        assert_eq!(mate_distance(500), None);
+    }
+
+    #[test]
+    fn test_no_legal_moves() {
+        // Stalemate
+        let result = search_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1", 3);
+
+        assert_eq!(result.score, DRAW_SCORE);
+        assert_eq!(result.best_move, None);
+        assert!(result.principal_variation.is_empty());
+
+        // Checkmate
+        let result = search_fen("7k/6Q1/6K1/8/8/8/8/8 b - - 0 1", 3);
+
+        assert_eq!(result.score, -MATE_SCORE);
+        assert_eq!(result.best_move, None);
+    }
+
+    #[test]
+    fn test_reuses_transpositions() {
+        let board = Board::from_fen(STARTING_FEN).unwrap();
+        let mut searcher = Searcher::new();
+
+        let first = searcher.search(&board, 4);
+        let second = searcher.search(&board, 4);
+
+        assert_eq!(second.score, first.score);
+        assert!(second.nodes < first.nodes);
+        assert!(searcher.transposition_table.hit_rate() > 0.0);
+    }
+
+    #[test]
+    fn test_fifty_move_draw_with_transpositions() {
+        let mut searcher = Searcher::new();
+
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 80").unwrap();
+        assert_eq!(searcher.search(&board, 3).score, 500);
+
+        // Same key, but every move ends the game in a draw
+        let drawn = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 99 80").unwrap();
+
+        assert_eq!(searcher.search(&drawn, 3).score, DRAW_SCORE);
+        assert_eq!(Searcher::new().search(&drawn, 3).score, DRAW_SCORE);
+    }
+
+    #[test]
+    fn test_depth_zero() {
+        // Only the quiescence search runs, which still sees Rxd5
+        let result = search_fen("4k3/8/8/3q4/8/8/8/3RK3 w - - 0 1", 0);
+
+        assert_eq!(result.score, 500);
+        assert_eq!(result.best_move, None);
+        assert_eq!(result.nodes, 2);
+    }
+}
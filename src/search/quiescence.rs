@@ -0,0 +1,217 @@
+use super::{evaluate, Searcher, DRAW_SCORE, INFINITY, MATE_SCORE, MAX_PLY};
+use crate::chess::{BitBoard, Board, GenType, Move, MoveType};
+
+// How much a capture can be worth beyond the material it wins, through
+//  what it does to the position, before it's not worth searching
+const DELTA_MARGIN: i32 = 200;
+
+impl Searcher {
+    // Searches captures and promotions until the position goes quiet, so a
+    //  leaf is never scored in the middle of an exchange.  The player to move
+    //  can stand pat on the static evaluation instead, unless they're in
+    //  check, in which case every way out of it is searched.
+    pub(super) fn quiescence(
+        &mut self,
+        board: &mut Board,
+        ply: u32,
+        mut alpha: i32,
+        beta: i32,
+    ) -> i32 {
+        self.nodes += 1;
+
+        if self.should_stop() {
+            return DRAW_SCORE;
+        }
+
+        if board.is_king_attacked(board.next_player) {
+            return self.quiescence_evasions(board, ply, alpha, beta);
+        }
+
+        let stand_pat = evaluate(board, &self.values);
+
+        if stand_pat >= beta || ply >= MAX_PLY {
+            return stand_pat;
+        }
+
+        alpha = alpha.max(stand_pat);
+
+        let mut moves = board.legal_moves_unannotated(GenType::Captures);
+        moves.sort_by_key(|mv| -self.material_gain(board, mv));
+
+        let mut best_score = stand_pat;
+
+        for mv in moves.iter() {
+            // Even winning the material and then some can't reach alpha
+            if stand_pat + self.material_gain(board, mv) + DELTA_MARGIN <= alpha {
+                continue;
+            }
+
+            if self.see_pruning && board.see(mv) < 0 {
+                continue;
+            }
+
+            let undo = board.make_move(mv);
+            let score = -self.quiescence(board, ply + 1, -beta, -alpha);
+            board.unmake_move(mv, undo);
+
+            if self.is_stopped {
+                return DRAW_SCORE;
+            }
+
+            if score > best_score {
+                best_score = score;
+                alpha = alpha.max(score);
+            }
+
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        best_score
+    }
+
+    fn quiescence_evasions(
+        &mut self,
+        board: &mut Board,
+        ply: u32,
+        mut alpha: i32,
+        beta: i32,
+    ) -> i32 {
+        let moves = board.legal_moves_unannotated(GenType::Evasions);
+
+        if moves.is_empty() {
+            return -MATE_SCORE + ply as i32;
+        }
+
+        if ply >= MAX_PLY {
+            return evaluate(board, &self.values);
+        }
+
+        let mut best_score = -INFINITY;
+
+        for mv in moves.iter() {
+            let undo = board.make_move(mv);
+            let score = -self.quiescence(board, ply + 1, -beta, -alpha);
+            board.unmake_move(mv, undo);
+
+            if self.is_stopped {
+                return DRAW_SCORE;
+            }
+
+            if score > best_score {
+                best_score = score;
+                alpha = alpha.max(score);
+            }
+
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        best_score
+    }
+
+    // The material `mv` wins outright, without counting any recapture
+    fn material_gain(&self, board: &Board, mv: &Move) -> i32 {
+        let captured = match mv.move_type {
+            MoveType::EnPassant => self.values.pawn,
+            _ => {
+                let target = BitBoard::from(mv.to);
+
+                board
+                    .pieces
+                    .iter()
+                    .position(|pieces| !pieces.intersect(target).is_empty())
+                    .and_then(num::FromPrimitive::from_usize)
+                    .map_or(0, |piece_type| self.values.value(piece_type))
+            }
+        };
+
+        let promoted = match mv.move_type {
+            MoveType::Promotion { promoted_to } => {
+                self.values.value(promoted_to) - self.values.pawn
+            }
+            _ => 0,
+        };
+
+        captured + promoted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess::STARTING_FEN;
+    use crate::search::search;
+
+    fn quiescence_fen(searcher: &mut Searcher, fen: &str, alpha: i32) -> i32 {
+        let mut board = Board::from_fen(fen).unwrap();
+
+        searcher.quiescence(&mut board, 0, alpha, INFINITY)
+    }
+
+    #[test]
+    fn test_stands_pat_when_quiet() {
+        let mut searcher = Searcher::new();
+
+        assert_eq!(quiescence_fen(&mut searcher, STARTING_FEN, -INFINITY), 0);
+        assert_eq!(searcher.nodes, 1);
+    }
+
+    #[test]
+    fn test_resolves_exchanges() {
+        // At depth 1 the queen would take the pawn, not seeing the recapture
+        let fen = "4k3/2p5/3p4/8/8/8/8/3QK3 w - - 0 1";
+        let result = search(&Board::from_fen(fen).unwrap(), 1);
+
+        assert_eq!(result.score, 700);
+        assert_ne!(
+            result.best_move.map(|mv| mv.to_uci()),
+            Some("d1d6".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_searches_evasions_in_check() {
+        // Standing pat would score even material, but the checking queen
+        //  can be taken
+        let fen = "1k6/8/8/8/8/8/6q1/Q6K w - - 0 1";
+        assert_eq!(quiescence_fen(&mut Searcher::new(), fen, -INFINITY), 900);
+
+        let mated = "7k/6Q1/6K1/8/8/8/8/8 b - - 0 1";
+        assert_eq!(
+            quiescence_fen(&mut Searcher::new(), mated, -INFINITY),
+            -MATE_SCORE
+        );
+    }
+
+    #[test]
+    fn test_delta_pruning() {
+        // Winning a pawn can't get within the margin of an alpha a rook up
+        let fen = "4k3/8/8/8/8/3p4/8/3QK3 w - - 0 1";
+        let mut searcher = Searcher::new();
+
+        assert_eq!(quiescence_fen(&mut searcher, fen, 800 + 500), 800);
+        assert_eq!(searcher.nodes, 1);
+
+        let mut searcher = Searcher::new();
+
+        assert_eq!(quiescence_fen(&mut searcher, fen, -INFINITY), 900);
+        assert_eq!(searcher.nodes, 2);
+    }
+
+    #[test]
+    fn test_see_pruning() {
+        let fen = "4k3/2p5/3p4/8/8/8/8/3QK3 w - - 0 1";
+
+        let mut searcher = Searcher::new();
+        assert_eq!(quiescence_fen(&mut searcher, fen, -INFINITY), 700);
+        assert_eq!(searcher.nodes, 1);
+
+        let mut searcher = Searcher::new();
+        searcher.see_pruning = false;
+        assert_eq!(quiescence_fen(&mut searcher, fen, -INFINITY), 700);
+        assert_eq!(searcher.nodes, 3);
+    }
+}
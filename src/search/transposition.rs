@@ -0,0 +1,257 @@
+use std::mem;
+
+use super::{MATE_SCORE, MAX_PLY};
+use crate::chess::Move;
+
+pub const DEFAULT_SIZE_MB: usize = 16;
+
+const BYTES_PER_MB: usize = 1024 * 1024;
+
+/// How a stored score relates to the position's real score, which depends
+///  on whether the search that stored it failed high, low, or neither.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Bound {
+    Exact,
+    /// The real score is at least this, from a beta cutoff
+    Lower,
+    /// The real score is at most this, as every move failed low
+    Upper,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableEntry {
+    /// The full zobrist key, to tell apart positions sharing a slot
+    pub key: u64,
+    /// How many plies were searched below the position
+    pub depth: u32,
+    /// Relative to the position itself, so mate scores count plies from
+    ///  here rather than from the root
+    pub score: i32,
+    pub bound: Bound,
+    pub best_move: Option<Move>,
+    /// Which search stored it, see `TranspositionTable::new_search`
+    pub age: u8,
+}
+
+/// A fixed-size cache of search results indexed by zobrist key.  Each key
+///  has one slot, which goes to the deeper search unless the one already
+///  there is left over from an earlier search.
+#[derive(Debug)]
+pub struct TranspositionTable {
+    entries: Vec<Option<TableEntry>>,
+    age: u8,
+    probes: u64,
+    hits: u64,
+}
+
+impl Default for TranspositionTable {
+    fn default() -> Self {
+        TranspositionTable::new(DEFAULT_SIZE_MB)
+    }
+}
+
+impl TranspositionTable {
+    /// A table taking up about `size_mb` megabytes, with at least one slot.
+    pub fn new(size_mb: usize) -> Self {
+        let mut table = TranspositionTable {
+            entries: vec![],
+            age: 0,
+            probes: 0,
+            hits: 0,
+        };
+        table.resize(size_mb);
+
+        table
+    }
+
+    /// Resizes to about `size_mb` megabytes, throwing away every entry.
+    pub fn resize(&mut self, size_mb: usize) {
+        let slot_count = (size_mb * BYTES_PER_MB / mem::size_of::<Option<TableEntry>>()).max(1);
+
+        self.entries = vec![None; slot_count];
+        self.clear();
+    }
+
+    pub fn clear(&mut self) {
+        for entry in self.entries.iter_mut() {
+            *entry = None;
+        }
+
+        self.age = 0;
+        self.probes = 0;
+        self.hits = 0;
+    }
+
+    pub fn slot_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Marks everything stored so far as old, so it gives way to whatever
+    ///  the next search stores.  Called at the start of each search.
+    pub fn new_search(&mut self) {
+        self.age = self.age.wrapping_add(1);
+    }
+
+    /// The entry for `key`, with its score made relative to the root of a
+    ///  search that reached the position `ply` plies in.
+    pub fn probe(&mut self, key: u64, ply: u32) -> Option<TableEntry> {
+        self.probes += 1;
+
+        let index = self.index(key);
+        let mut entry = match &self.entries[index] {
+            Some(entry) if entry.key == key => entry.clone(),
+            _ => return None,
+        };
+
+        self.hits += 1;
+        entry.score = score_from_table(entry.score, ply);
+
+        Some(entry)
+    }
+
+    /// Stores a search result for the position `ply` plies into the search,
+    ///  unless its slot holds a deeper result from this same search.
+    pub fn store(
+        &mut self,
+        key: u64,
+        ply: u32,
+        depth: u32,
+        score: i32,
+        bound: Bound,
+        best_move: Option<Move>,
+    ) {
+        let index = self.index(key);
+
+        let should_replace = match &self.entries[index] {
+            Some(entry) => entry.key == key || entry.age != self.age || depth >= entry.depth,
+            None => true,
+        };
+
+        if should_replace {
+            self.entries[index] = Some(TableEntry {
+                key,
+                depth,
+                score: score_to_table(score, ply),
+                bound,
+                best_move,
+                age: self.age,
+            });
+        }
+    }
+
+    pub fn probes(&self) -> u64 {
+        self.probes
+    }
+
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// The fraction of probes that found their position, from 0 to 1.
+    pub fn hit_rate(&self) -> f64 {
+        if self.probes == 0 {
+            return 0.0;
+        }
+
+        self.hits as f64 / self.probes as f64
+    }
+
+    fn index(&self, key: u64) -> usize {
+        (key % self.entries.len() as u64) as usize
+    }
+}
+
+// Mate scores count plies from the root, but a position can be reached at
+//  any ply, so they're stored counting from the position instead
+fn score_to_table(score: i32, ply: u32) -> i32 {
+    if score > MATE_SCORE - MAX_PLY as i32 {
+        score + ply as i32
+    } else if score < -MATE_SCORE + MAX_PLY as i32 {
+        score - ply as i32
+    } else {
+        score
+    }
+}
+
+fn score_from_table(score: i32, ply: u32) -> i32 {
+    if score > MATE_SCORE - MAX_PLY as i32 {
+        score - ply as i32
+    } else if score < -MATE_SCORE + MAX_PLY as i32 {
+        score + ply as i32
+    } else {
+        score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_store_and_probe() {
+        let mut table = TranspositionTable::new(1);
+
+        assert!(table.slot_count() > 1);
+        assert_eq!(table.probe(42, 0), None);
+
+        table.store(42, 0, 3, 150, Bound::Lower, Some(Move::default()));
+
+        let entry = table.probe(42, 5).unwrap();
+        assert_eq!(entry.depth, 3);
+        assert_eq!(entry.score, 150);
+        assert_eq!(entry.bound, Bound::Lower);
+        assert_eq!(entry.best_move, Some(Move::default()));
+
+        // Same slot, different position
+        assert_eq!(table.probe(42 + table.slot_count() as u64, 0), None);
+
+        assert_eq!(table.probes(), 3);
+        assert_eq!(table.hits(), 1);
+        assert!((table.hit_rate() - 1.0 / 3.0).abs() < 1e-9);
+
+        table.resize(0);
+        assert_eq!(table.slot_count(), 1);
+        assert_eq!(table.probe(42, 0), None);
+        assert_eq!(table.probes(), 1);
+    }
+
+    #[test]
+    fn test_mate_scores_follow_ply() {
+        let mut table = TranspositionTable::new(1);
+
+        // Mate 3 plies after a position found 4 plies in
+        table.store(7, 4, 3, MATE_SCORE - 7, Bound::Exact, None);
+        table.store(8, 4, 3, -MATE_SCORE + 7, Bound::Exact, None);
+
+        assert_eq!(table.probe(7, 4).unwrap().score, MATE_SCORE - 7);
+        assert_eq!(table.probe(7, 2).unwrap().score, MATE_SCORE - 5);
+        assert_eq!(table.probe(8, 2).unwrap().score, -MATE_SCORE + 5);
+
+        table.store(9, 4, 3, -250, Bound::Upper, None);
+        assert_eq!(table.probe(9, 2).unwrap().score, -250);
+    }
+
+    #[test]
+    fn test_replacement() {
+        let mut table = TranspositionTable::new(0);
+
+        table.store(1, 0, 5, 10, Bound::Exact, None);
+
+        // A shallower result from the same search doesn't replace it
+        table.store(2, 0, 4, 20, Bound::Exact, None);
+        assert_eq!(table.probe(1, 0).unwrap().score, 10);
+
+        // A deeper one does
+        table.store(2, 0, 6, 20, Bound::Exact, None);
+        assert_eq!(table.probe(2, 0).unwrap().score, 20);
+
+        // Anything from a newer search does, however shallow
+        table.new_search();
+        table.store(3, 0, 1, 30, Bound::Exact, None);
+        assert_eq!(table.probe(3, 0).unwrap().score, 30);
+
+        // As does the same position searched again
+        table.store(3, 0, 0, 40, Bound::Upper, None);
+        assert_eq!(table.probe(3, 0).unwrap().score, 40);
+    }
+}
@@ -9,6 +9,7 @@ pub fn generate_moves_for_board(
 
     let mut board = Board::from(board, player).unwrap();
     board.prev_move = prev_move;
+    board.zobrist_key = board.compute_zobrist_key();
     boards.push(format!("{}", board).to_owned());
 
     for board in board.generate_moves() {
@@ -15,3 +15,13 @@ fn test_generate_white_pawn_moves() -> Vec<String> {
 fn test_generate_black_pawn_moves() -> Vec<String> {
     generate_moves_for_board(BLACK_PAWN_TEST, Player::Black, Some(BLACK_EN_PASSANT))
 }
+
+#[snapshot]
+fn test_generate_white_promotions() -> Vec<String> {
+    generate_moves_for_board(WHITE_PROMOTION_TEST, Player::White, None)
+}
+
+#[snapshot]
+fn test_generate_black_promotions() -> Vec<String> {
+    generate_moves_for_board(BLACK_PROMOTION_TEST, Player::Black, None)
+}